@@ -4,7 +4,15 @@ import { useConnection, useWallet } from '@solana/wallet-adapter-react';
 import { PublicKey, SystemProgram, LAMPORTS_PER_SOL } from '@solana/web3.js';
 import { Program, AnchorProvider, BN, Idl } from '@coral-xyz/anchor';
 import { useMemo, useCallback, useState } from 'react';
-import { VAULT_SEED, JSOLI_MINT_SEED, USER_ACCOUNT_SEED } from '../../config';
+import {
+    VAULT_SEED,
+    JSOLI_MINT_SEED,
+    USER_ACCOUNT_SEED,
+    WITHDRAW_REQUEST_SEED,
+    WITHDRAW_ESCROW_SEED,
+    VAULT_SOL_SEED,
+    PROTOCOL_CONFIG_SEED,
+} from '../../config';
 
 // Import IDL - Anchor 0.30+ format with address/metadata
 import idlJson from '../idl/jsol_vault.json';
@@ -27,6 +35,19 @@ interface UserAccount {
     // Add other fields as needed
 }
 
+interface WithdrawRequest {
+    owner: PublicKey;
+    shares: BN;
+    estimatedLamports: BN;
+    readyEpoch: BN;
+    readyAt: BN;
+    requestIndex: BN;
+    status: Record<string, object>;
+}
+
+// Offset of WithdrawRequest.owner: 8-byte discriminator + 1-byte bump
+const WITHDRAW_REQUEST_OWNER_OFFSET = 9;
+
 export function useProgram() {
     const { connection } = useConnection();
     const wallet = useWallet();
@@ -80,7 +101,15 @@ export function useProgram() {
             [Buffer.from(JSOLI_MINT_SEED)],
             PROGRAM_ID
         );
-        return { vaultPda, jsoliMintPda };
+        const [withdrawEscrowPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from(WITHDRAW_ESCROW_SEED)],
+            PROGRAM_ID
+        );
+        const [vaultSolPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from(VAULT_SOL_SEED)],
+            PROGRAM_ID
+        );
+        return { vaultPda, jsoliMintPda, withdrawEscrowPda, vaultSolPda };
     }, []);
 
     // Get user account PDA
@@ -150,38 +179,67 @@ export function useProgram() {
             const sharesLamports = new BN(Math.floor(sharesToBurn * LAMPORTS_PER_SOL));
             const userAccountPda = getUserAccountPda(wallet.publicKey);
 
-            // First, fetch the user account to get the current pending_withdrawals count
+            // First, fetch the user account to get the next request index
             // This is needed to derive the correct withdraw_request PDA
             const userAccount = await (program.account as any).userAccount.fetch(userAccountPda);
-            const requestIndex = userAccount.pendingWithdrawals as number;
+            const requestIndex = userAccount.nextRequestIndex as BN;
 
             // Derive withdraw request PDA with request index
-            // Seeds: [b"withdraw", user_pubkey, pending_withdrawals.to_le_bytes(1)]
-            const requestIndexBuffer = Buffer.alloc(1);
-            requestIndexBuffer.writeUInt8(requestIndex);
+            // Seeds: [b"withdraw", user_pubkey, next_request_index.to_le_bytes(8)]
+            const requestIndexBuffer = requestIndex.toArrayLike(Buffer, 'le', 8);
 
             const [withdrawRequestPda] = PublicKey.findProgramAddressSync(
                 [
-                    Buffer.from('withdraw'),
+                    Buffer.from(WITHDRAW_REQUEST_SEED),
                     wallet.publicKey.toBuffer(),
                     requestIndexBuffer
                 ],
                 PROGRAM_ID
             );
 
-            console.log('[Withdraw] Request index:', requestIndex);
+            console.log('[Withdraw] Request index:', requestIndex.toString());
             console.log('[Withdraw] PDA:', withdrawRequestPda.toString());
 
+            // The jSOLi moves from the user's ATA into the vault's escrow
+            const { getAssociatedTokenAddress, TOKEN_PROGRAM_ID } = await import('@solana/spl-token');
+            const userJsoliAccount = await getAssociatedTokenAddress(
+                pdas.jsoliMintPda,
+                wallet.publicKey
+            );
+
+            // The registry entry of every allocation, in allocation order,
+            // sets how long the request waits if idle SOL can't cover it
+            const vault = await (program.account as any).vaultState.fetch(pdas.vaultPda);
+            const protocolConfigs = vault.allocations
+                .slice(0, vault.numAllocations)
+                .map((alloc: { protocol: number }) => ({
+                    pubkey: PublicKey.findProgramAddressSync(
+                        [Buffer.from(PROTOCOL_CONFIG_SEED), Buffer.from([alloc.protocol])],
+                        PROGRAM_ID
+                    )[0],
+                    isWritable: false,
+                    isSigner: false,
+                }));
+
             // Use requestWithdraw (camelCase) - Anchor converts snake_case to camelCase
+            // A null payout protocol settles the request in SOL
             const tx = await program.methods
-                .requestWithdraw(sharesLamports)
+                .requestWithdraw(sharesLamports, null)
                 .accounts({
                     vault: pdas.vaultPda,
                     userAccount: userAccountPda,
                     withdrawRequest: withdrawRequestPda,
+                    jsoliMint: pdas.jsoliMintPda,
+                    userJsolAccount: userJsoliAccount,
+                    withdrawEscrow: pdas.withdrawEscrowPda,
+                    vaultSolAccount: pdas.vaultSolPda,
+                    protocolConfig: null,
+                    vaultLstAccount: null,
                     user: wallet.publicKey,
+                    tokenProgram: TOKEN_PROGRAM_ID,
                     systemProgram: SystemProgram.programId,
                 } as any)
+                .remainingAccounts(protocolConfigs)
                 .rpc();
 
             console.log('Withdraw request successful:', tx);
@@ -222,6 +280,32 @@ export function useProgram() {
         }
     }, [program, wallet.publicKey, getUserAccountPda]);
 
+    // Fetch the user's open withdraw requests
+    // Settled requests are closed on-chain, so every remaining one is open
+    const getOpenWithdrawRequests = useCallback(async (): Promise<WithdrawRequest[]> => {
+        if (!program || !wallet.publicKey) return [];
+        try {
+            const withdrawRequests = (program.account as any).withdrawRequest;
+            const requests = await withdrawRequests.all([
+                // Skip accounts in the legacy layout, which don't decode
+                { dataSize: withdrawRequests.size },
+                {
+                    memcmp: {
+                        offset: WITHDRAW_REQUEST_OWNER_OFFSET,
+                        bytes: wallet.publicKey.toBase58(),
+                    },
+                },
+            ]);
+            return requests
+                .map((r: any) => r.account as WithdrawRequest)
+                .filter((r: WithdrawRequest) => 'pending' in r.status)
+                .sort((a: WithdrawRequest, b: WithdrawRequest) => a.requestIndex.cmp(b.requestIndex));
+        } catch (e) {
+            console.error('Failed to fetch withdraw requests:', e);
+            return [];
+        }
+    }, [program, wallet.publicKey]);
+
     return {
         program,
         provider,
@@ -230,6 +314,7 @@ export function useProgram() {
         withdraw,
         getVaultState,
         getUserAccount,
+        getOpenWithdrawRequests,
         loading,
         error,
         connected: !!wallet.publicKey && !!program,
@@ -38,6 +38,9 @@ export const VAULT_SEED = 'vault';
 export const JSOLI_MINT_SEED = 'jsoli_mint'; // Note: with 'i'
 export const USER_ACCOUNT_SEED = 'user';
 export const WITHDRAW_REQUEST_SEED = 'withdraw';
+export const WITHDRAW_ESCROW_SEED = 'withdraw_escrow';
+export const VAULT_SOL_SEED = 'vault_sol';
+export const PROTOCOL_CONFIG_SEED = 'protocol';
 
 // Token Decimals
 export const SOL_DECIMALS = 9;
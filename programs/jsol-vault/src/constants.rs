@@ -20,9 +20,33 @@ pub const USER_ACCOUNT_SEED: &[u8] = b"user";
 /// Seed for withdraw request PDA
 pub const WITHDRAW_REQUEST_SEED: &[u8] = b"withdraw";
 
+/// Seed for the token account escrowing jSOLi of queued withdrawals
+pub const WITHDRAW_ESCROW_SEED: &[u8] = b"withdraw_escrow";
+
 /// Seed for fee collector PDA
 pub const FEE_COLLECTOR_SEED: &[u8] = b"fees";
 
+/// Seed for the vault's SOL holding PDA
+pub const VAULT_SOL_SEED: &[u8] = b"vault_sol";
+
+/// Seed for protocol registry PDAs
+pub const PROTOCOL_CONFIG_SEED: &[u8] = b"protocol";
+
+/// Seed for the vault's per-mint LST custody token accounts
+pub const CUSTODY_SEED: &[u8] = b"custody";
+
+/// Seed for the per-user temporary token account used to unwrap wSOL deposits
+pub const WSOL_TEMP_SEED: &[u8] = b"wsol_temp";
+
+/// Seed for per-protocol pending delayed-unstake lists
+pub const PENDING_UNSTAKES_SEED: &[u8] = b"pending_unstakes";
+
+/// Seed for vault-created delayed-unstake ticket accounts
+pub const UNSTAKE_TICKET_SEED: &[u8] = b"unstake_ticket";
+
+/// Seed for oracle feed configuration PDAs
+pub const ORACLE_FEED_SEED: &[u8] = b"oracle_feed";
+
 // ============================================================================
 // Fee Configuration
 // ============================================================================
@@ -52,6 +76,9 @@ pub const TOTAL_ALLOCATION_BPS: u16 = 10000;
 /// Maximum number of supported protocols
 pub const MAX_PROTOCOLS: usize = 10;
 
+/// Maximum open delayed-unstake tickets per protocol
+pub const MAX_PENDING_TICKETS: usize = 16;
+
 // ============================================================================
 // Limits
 // ============================================================================
@@ -63,6 +90,18 @@ pub const MIN_DEPOSIT_LAMPORTS: u64 = 100_000_000;
 /// These shares are effectively "dead" and protect against share price manipulation
 pub const MINIMUM_INITIAL_SHARES: u64 = 1_000;
 
+/// Maximum instant unstake fee accepted in the registry: 10% (1000 bps)
+pub const MAX_INSTANT_UNSTAKE_FEE_BPS: u16 = 1000;
+
+/// Maximum haircut applied to direct LST deposits: 10% (1000 bps)
+pub const MAX_LST_DEPOSIT_HAIRCUT_BPS: u16 = 1000;
+
+/// Default fee on withdrawals paid out in a single LST: 0.1% (10 bps)
+pub const DEFAULT_LST_PAYOUT_FEE_BPS: u16 = 10;
+
+/// Maximum fee on withdrawals paid out in a single LST: 10% (1000 bps)
+pub const MAX_LST_PAYOUT_FEE_BPS: u16 = 1000;
+
 /// Number of epochs for unstaking
 pub const UNSTAKING_EPOCHS: u8 = 1;
 
@@ -80,12 +119,27 @@ pub const SHARE_PRECISION: u64 = 1_000_000_000;
 // Oracle Configuration
 // ============================================================================
 
-/// Maximum oracle staleness in seconds
+/// Maximum oracle staleness in seconds (default for newly configured feeds)
 pub const MAX_ORACLE_STALENESS_SECS: i64 = 60;
 
+/// Maximum number of price sources configured for one oracle feed
+pub const MAX_ORACLE_SOURCES: usize = 3;
+
+/// Exponent all oracle prices are normalized to before aggregation
+pub const ORACLE_PRICE_EXPONENT: i32 = -8;
+
+/// Default maximum oracle confidence interval: 2% of price (200 bps)
+pub const DEFAULT_MAX_CONFIDENCE_BPS: u16 = 200;
+
 /// Maximum allowed oracle price deviation: 5%
 pub const MAX_ORACLE_DEVIATION_BPS: u16 = 500;
 
+/// Default maximum NAV age for deposits and withdrawals: 150 slots (~1 minute)
+pub const DEFAULT_MAX_NAV_STALENESS_SLOTS: u64 = 150;
+
+/// Default maximum LST exchange rate move per epoch: 0.5% (50 bps)
+pub const DEFAULT_MAX_RATE_CHANGE_BPS_PER_EPOCH: u16 = 50;
+
 // ============================================================================
 // Rebalance Configuration
 // ============================================================================
@@ -112,6 +166,22 @@ pub const BLAZESTAKE_PROGRAM_ID: &str = "stk9ApL5HeVAwPLr3TLhDXdZS8ptVu7zp6ov8HF
 /// Jupiter Staked SOL Program ID
 pub const JUPITER_PROGRAM_ID: &str = "jupSoLaHXQiZZTSfEWMTRRgpnyFm8f6sZdosWBjx93v";
 
+/// Pyth Solana Receiver Program ID (owner of PriceUpdateV2 accounts)
+pub const PYTH_RECEIVER_PROGRAM_ID: &str = "rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ";
+
+/// Switchboard On-Demand Program ID (owner of pull feed accounts)
+pub const SWITCHBOARD_ON_DEMAND_PROGRAM_ID: &str = "SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv";
+
+// ============================================
+// Stake Pool Accounts (Mainnet)
+// ============================================
+
+/// Jito SPL stake pool account
+pub const JITO_STAKE_POOL: &str = "Jito4APyf642JPZPx3hGc6WWJ8zPKtRbRs4P815Awbb";
+
+/// Marinade Finance state account
+pub const MARINADE_STATE: &str = "8szGkuLTAux9XMgZ2vtY39jVSowEcpBfFfD8hXSEqdGC";
+
 // ============================================
 // LST Token Mints (Mainnet)
 // ============================================
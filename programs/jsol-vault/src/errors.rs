@@ -38,6 +38,10 @@ pub enum VaultError {
     #[msg("Duplicate protocol in allocation list")]
     DuplicateProtocol,
     
+    /// Protocol dropped from the allocations still holds vault funds
+    #[msg("Cannot drop a protocol that still holds vault funds")]
+    AllocationNotEmpty,
+    
     // ========================================================================
     // Fee Errors (6040-6059)
     // ========================================================================
@@ -50,6 +54,10 @@ pub enum VaultError {
     #[msg("Performance fee cannot exceed 2000 basis points (20%)")]
     PerformanceFeeExceedsMax,
     
+    /// LST payout fee exceeds maximum
+    #[msg("LST payout fee cannot exceed 1000 basis points (10%)")]
+    LstPayoutFeeExceedsMax,
+    
     // ========================================================================
     // Deposit Errors (6060-6079)
     // ========================================================================
@@ -70,6 +78,14 @@ pub enum VaultError {
     #[msg("Amount must be greater than zero")]
     ZeroAmount,
     
+    /// Stake account is not an active delegation owned by the depositor
+    #[msg("Stake account is not an active delegation controlled by the depositor")]
+    InvalidStakeAccount,
+    
+    /// Stake account lockup is still in force
+    #[msg("Stake account is locked up")]
+    StakeAccountLocked,
+    
     // ========================================================================
     // Withdrawal Errors (6080-6099)
     // ========================================================================
@@ -130,6 +146,30 @@ pub enum VaultError {
     #[msg("Oracle is not available")]
     OracleUnavailable,
     
+    /// Oracle update verification level is below the feed's requirement
+    #[msg("Oracle price update is not sufficiently verified")]
+    OracleVerificationTooLow,
+    
+    /// NAV has not been refreshed recently enough
+    #[msg("Vault NAV is stale, call update_nav first")]
+    StaleNav,
+    
+    /// Protocol is quarantined after an exchange rate jump
+    #[msg("Protocol is quarantined after an exchange rate jump")]
+    ProtocolQuarantined,
+    
+    /// Oracle feed configuration is invalid
+    #[msg("Invalid oracle feed configuration")]
+    InvalidOracleConfig,
+    
+    /// Oracle confidence interval is too wide relative to price
+    #[msg("Oracle confidence interval exceeds maximum allowed")]
+    OracleConfidenceTooWide,
+    
+    /// Fewer oracle sources returned a price than the feed requires
+    #[msg("Not enough oracle sources returned a valid price")]
+    OracleQuorumNotMet,
+    
     // ========================================================================
     // Authorization Errors (6140-6159)
     // ========================================================================
@@ -174,6 +214,34 @@ pub enum VaultError {
     #[msg("Protocol is temporarily unavailable")]
     ProtocolUnavailable,
     
+    /// Protocol account does not match the expected address
+    #[msg("Protocol account does not match the expected address")]
+    InvalidProtocolAccount,
+    
+    /// Protocol is registered but disabled
+    #[msg("Protocol is disabled in the registry")]
+    ProtocolDisabled,
+    
+    /// Instant unstake fee exceeds maximum
+    #[msg("Instant unstake fee exceeds maximum allowed")]
+    InstantUnstakeFeeExceedsMax,
+    
+    /// LST deposit haircut exceeds maximum
+    #[msg("LST deposit haircut exceeds maximum allowed")]
+    LstDepositHaircutExceedsMax,
+    
+    /// Accounts required by the protocol were not provided
+    #[msg("Missing accounts required by the protocol")]
+    MissingProtocolAccounts,
+    
+    /// No room for another delayed-unstake ticket
+    #[msg("Too many pending unstake tickets for this protocol")]
+    PendingUnstakesFull,
+    
+    /// Ticket is not in the protocol's pending list
+    #[msg("Unstake ticket is not pending for this protocol")]
+    UnstakeTicketNotFound,
+    
     // ========================================================================
     // Account Errors (6200-6219)
     // ========================================================================
@@ -189,4 +257,8 @@ pub enum VaultError {
     /// Invalid mint
     #[msg("Invalid token mint")]
     InvalidMint,
+    
+    /// Token account is not the expected one
+    #[msg("Invalid token account")]
+    InvalidTokenAccount,
 }
@@ -1,10 +1,14 @@
 //! Admin instructions for vault management
 
 use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::constants::*;
 use crate::errors::VaultError;
 use crate::state::*;
+use crate::utils::cpi::marinade;
+use crate::utils::stake_pool::SplStakePoolAdapter;
+use crate::utils::validation::{validate_address, validate_protocol_id};
 
 /// Update vault configuration
 /// 
@@ -90,6 +94,76 @@ pub fn update_config_handler(
         vault.config.deposit_cap = cap;
     }
     
+    // Update NAV staleness limit if provided
+    if let Some(slots) = new_config.max_nav_staleness_slots {
+        emit!(ConfigUpdatedEvent {
+            field: "max_nav_staleness_slots".to_string(),
+            old_value: vault.config.max_nav_staleness_slots,
+            new_value: slots,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        vault.config.max_nav_staleness_slots = slots;
+    }
+    
+    // Update exchange rate guard if provided
+    if let Some(max_change) = new_config.max_rate_change_bps_per_epoch {
+        emit!(ConfigUpdatedEvent {
+            field: "max_rate_change_bps_per_epoch".to_string(),
+            old_value: vault.config.max_rate_change_bps_per_epoch as u64,
+            new_value: max_change as u64,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        vault.config.max_rate_change_bps_per_epoch = max_change;
+    }
+    
+    // Update oracle confidence limit if provided
+    if let Some(max_confidence) = new_config.max_confidence_bps {
+        require!(
+            max_confidence <= BPS_DENOMINATOR as u16,
+            VaultError::InvalidOracleConfig
+        );
+        
+        emit!(ConfigUpdatedEvent {
+            field: "max_confidence_bps".to_string(),
+            old_value: vault.config.max_confidence_bps as u64,
+            new_value: max_confidence as u64,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        vault.config.max_confidence_bps = max_confidence;
+    }
+    
+    // Update LST payout fee if provided
+    if let Some(fee) = new_config.lst_payout_fee_bps {
+        require!(
+            fee <= MAX_LST_PAYOUT_FEE_BPS,
+            VaultError::LstPayoutFeeExceedsMax
+        );
+        
+        emit!(ConfigUpdatedEvent {
+            field: "lst_payout_fee_bps".to_string(),
+            old_value: vault.config.lst_payout_fee_bps as u64,
+            new_value: fee as u64,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        vault.config.lst_payout_fee_bps = fee;
+    }
+    
+    // Update settlement policy if provided
+    if let Some(policy) = new_config.settlement_policy {
+        emit!(ConfigUpdatedEvent {
+            field: "settlement_policy".to_string(),
+            old_value: vault.config.settlement_policy as u64,
+            new_value: policy as u64,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        vault.config.settlement_policy = policy;
+    }
+    
     msg!("Vault configuration updated");
     
     Ok(())
@@ -133,6 +207,83 @@ pub fn unpause_handler(ctx: Context<AdminAction>) -> Result<()> {
     Ok(())
 }
 
+/// Clear a protocol's rate-guard quarantine
+/// 
+/// Only the vault authority can call this instruction. The stored rate is
+/// reset, so the next NAV refresh accepts the protocol's current rate as
+/// the new baseline.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all accounts
+/// * `protocol` - Protocol to release
+pub fn clear_quarantine_handler(ctx: Context<AdminAction>, protocol: u8) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    
+    let index = vault.allocations[..vault.num_allocations as usize]
+        .iter()
+        .position(|alloc| alloc.protocol == protocol)
+        .ok_or(VaultError::UnsupportedProtocol)?;
+    
+    let alloc = &mut vault.allocations[index];
+    require!(alloc.is_quarantined, VaultError::InvalidVaultState);
+    alloc.is_quarantined = false;
+    alloc.last_rate = 0;
+    alloc.last_rate_slot = 0;
+    alloc.last_rate_epoch = 0;
+    
+    emit!(QuarantineClearedEvent {
+        protocol,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Protocol {} released from quarantine", protocol);
+    
+    Ok(())
+}
+
+/// Migrate the vault account from the legacy layout
+/// 
+/// Only the vault authority can call this instruction. The vault written by
+/// the first deployment no longer deserializes as `VaultState`, so every
+/// other instruction fails for it until it is migrated. The account is
+/// grown to `VaultState::LEN`, with the authority topping up its rent, and
+/// the legacy fields are rewritten into the current layout. Settings added
+/// since take their defaults, and a NAV refresh is required before
+/// deposits and withdrawals resume.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all accounts
+pub fn migrate_vault_handler(ctx: Context<MigrateVault>) -> Result<()> {
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let legacy = LegacyVaultState::try_from_account_data(&vault_info.try_borrow_data()?)?;
+    require_keys_eq!(legacy.authority, ctx.accounts.authority.key(), VaultError::Unauthorized);
+    
+    // Fund the larger account
+    let rent_exempt = Rent::get()?.minimum_balance(VaultState::LEN);
+    let top_up = rent_exempt.saturating_sub(vault_info.lamports());
+    if top_up > 0 {
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: vault_info.clone(),
+            },
+        );
+        anchor_lang::system_program::transfer(transfer_ctx, top_up)?;
+    }
+    
+    // Grow the account and rewrite it in the current layout
+    vault_info.realloc(VaultState::LEN, true)?;
+    let vault = legacy.migrate();
+    let mut data = vault_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data[..];
+    vault.try_serialize(&mut writer)?;
+    
+    msg!("Vault migrated from {} to {} bytes", LegacyVaultState::LEN, VaultState::LEN);
+    
+    Ok(())
+}
+
 /// Collect accumulated fees
 pub fn collect_fees_handler(ctx: Context<CollectFees>) -> Result<()> {
     let vault = &mut ctx.accounts.vault;
@@ -221,6 +372,14 @@ pub fn collect_fees_handler(ctx: Context<CollectFees>) -> Result<()> {
 }
 
 /// Update target allocations
+/// 
+/// Each protocol keeps its allocation state (amount, current weight, rate
+/// history and quarantine) wherever it lands in the new list. Protocols
+/// left out must hold no vault funds. NAV must be refreshed afterwards.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all accounts
+/// * `new_allocations` - Protocols and their target weights
 pub fn update_allocations_handler(
     ctx: Context<AdminAction>,
     new_allocations: Vec<UpdateAllocation>,
@@ -258,14 +417,252 @@ pub fn update_allocations_handler(
         VaultError::InvalidAllocationSum
     );
     
-    // Update allocations
+    apply_allocations(vault, &new_allocations)?;
+    
+    msg!("Allocations updated");
+    
+    Ok(())
+}
+
+/// Replace the vault's allocations with `new_allocations`
+/// 
+/// Allocation state is carried over by protocol ID; new protocols start
+/// empty without a rate history. Fails if a dropped protocol still holds
+/// funds. Clears `last_nav_slot` so the next priced action waits for a NAV
+/// update.
+pub fn apply_allocations(vault: &mut VaultState, new_allocations: &[UpdateAllocation]) -> Result<()> {
+    let current = &vault.allocations[..vault.num_allocations as usize];
+    for alloc in current {
+        let kept = new_allocations.iter().any(|new| new.protocol == alloc.protocol);
+        require!(kept || alloc.amount == 0, VaultError::AllocationNotEmpty);
+    }
+    
+    let mut allocations = [Allocation::default(); MAX_PROTOCOLS];
+    for (slot, new) in allocations.iter_mut().zip(new_allocations) {
+        if let Some(existing) = current.iter().find(|alloc| alloc.protocol == new.protocol) {
+            *slot = *existing;
+        }
+        slot.protocol = new.protocol;
+        slot.target_bps = new.target_bps;
+    }
+    
+    vault.allocations = allocations;
     vault.num_allocations = new_allocations.len() as u8;
-    for (i, alloc) in new_allocations.iter().enumerate() {
-        vault.allocations[i].protocol = alloc.protocol;
-        vault.allocations[i].target_bps = alloc.target_bps;
+    vault.last_nav_slot = 0;
+    Ok(())
+}
+
+/// Register a protocol in the registry
+/// 
+/// The protocol's pool or state account is decoded to confirm it belongs to
+/// the given program and mints the given LST before the entry is stored.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all accounts
+/// * `protocol` - Protocol ID to register
+/// * `params` - Adapter kind and unstaking terms
+pub fn register_protocol_handler(
+    ctx: Context<RegisterProtocol>,
+    protocol: u8,
+    params: RegisterProtocolParams,
+) -> Result<()> {
+    validate_protocol_id(protocol)?;
+    require!(params.unstake_delay >= 0, VaultError::InvalidVaultState);
+    require!(
+        params.instant_unstake_fee_bps <= MAX_INSTANT_UNSTAKE_FEE_BPS,
+        VaultError::InstantUnstakeFeeExceedsMax
+    );
+    require!(
+        params.lst_deposit_haircut_bps <= MAX_LST_DEPOSIT_HAIRCUT_BPS,
+        VaultError::LstDepositHaircutExceedsMax
+    );
+    require!(
+        ctx.accounts.protocol_program.executable,
+        VaultError::InvalidProtocolAccount
+    );
+    
+    let program_id = ctx.accounts.protocol_program.key();
+    let pool = ctx.accounts.pool.key();
+    let mint = ctx.accounts.mint.key();
+    
+    // Confirm the pool/state account matches the adapter
+    match params.adapter {
+        AdapterKind::SplStakePool => {
+            SplStakePoolAdapter::new(program_id, pool, mint).load_pool(&ctx.accounts.pool)?;
+        }
+        AdapterKind::Marinade => {
+            validate_address(&program_id, MARINADE_PROGRAM_ID)?;
+            validate_address(&mint, MSOL_MINT)?;
+            marinade::read_msol_price(&ctx.accounts.pool, &program_id, &mint)?;
+        }
     }
     
-    msg!("Allocations updated");
+    let config = &mut ctx.accounts.protocol_config;
+    config.bump = ctx.bumps.protocol_config;
+    config.protocol = protocol;
+    config.adapter = params.adapter;
+    config.program_id = program_id;
+    config.mint = mint;
+    config.pool = pool;
+    config.vault_token_account = ctx.accounts.vault_token_account.key();
+    config.unstake_delay = params.unstake_delay;
+    config.supports_instant_unstake = params.supports_instant_unstake;
+    config.instant_unstake_fee_bps = params.instant_unstake_fee_bps;
+    config.is_active = true;
+    config.lst_deposit_haircut_bps = params.lst_deposit_haircut_bps;
+    
+    emit!(ProtocolRegisteredEvent {
+        protocol,
+        adapter: params.adapter,
+        program_id,
+        mint,
+        pool,
+        vault_token_account: config.vault_token_account,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Protocol {} registered", protocol);
+    msg!("Program: {}", program_id);
+    msg!("Mint: {}", mint);
+    msg!("Pool: {}", pool);
+    msg!("Custody: {}", config.vault_token_account);
+    
+    Ok(())
+}
+
+/// Update a protocol registry entry
+/// 
+/// Only the vault authority can call this instruction.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all accounts
+/// * `params` - New registry values
+pub fn update_protocol_handler(
+    ctx: Context<UpdateProtocol>,
+    params: UpdateProtocolParams,
+) -> Result<()> {
+    let config = &mut ctx.accounts.protocol_config;
+    
+    if let Some(delay) = params.unstake_delay {
+        require!(delay >= 0, VaultError::InvalidVaultState);
+        config.unstake_delay = delay;
+    }
+    
+    if let Some(supported) = params.supports_instant_unstake {
+        config.supports_instant_unstake = supported;
+    }
+    
+    if let Some(fee) = params.instant_unstake_fee_bps {
+        require!(
+            fee <= MAX_INSTANT_UNSTAKE_FEE_BPS,
+            VaultError::InstantUnstakeFeeExceedsMax
+        );
+        config.instant_unstake_fee_bps = fee;
+    }
+    
+    if let Some(active) = params.is_active {
+        config.is_active = active;
+    }
+    
+    if let Some(haircut) = params.lst_deposit_haircut_bps {
+        require!(
+            haircut <= MAX_LST_DEPOSIT_HAIRCUT_BPS,
+            VaultError::LstDepositHaircutExceedsMax
+        );
+        config.lst_deposit_haircut_bps = haircut;
+    }
+    
+    emit!(ProtocolUpdatedEvent {
+        protocol: config.protocol,
+        unstake_delay: config.unstake_delay,
+        supports_instant_unstake: config.supports_instant_unstake,
+        instant_unstake_fee_bps: config.instant_unstake_fee_bps,
+        is_active: config.is_active,
+        lst_deposit_haircut_bps: config.lst_deposit_haircut_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Protocol {} updated", config.protocol);
+    
+    Ok(())
+}
+
+/// Create or update the configuration of a Pyth price feed
+/// 
+/// Only the vault authority can call this instruction.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all accounts
+/// * `feed_id` - Pyth feed ID the configuration applies to
+/// * `params` - Price sources, aggregation rule, staleness and
+///   verification requirements
+pub fn set_oracle_feed_handler(
+    ctx: Context<SetOracleFeed>,
+    feed_id: [u8; 32],
+    params: SetOracleFeedParams,
+) -> Result<()> {
+    // Zero selects the default staleness window
+    let max_staleness_secs = match params.max_staleness_secs {
+        0 => MAX_ORACLE_STALENESS_SECS,
+        secs => secs,
+    };
+    require!(max_staleness_secs > 0, VaultError::InvalidOracleConfig);
+    
+    // Validate sources and the aggregation rule
+    let num_sources = params.sources.len();
+    require!(
+        num_sources > 0 && num_sources <= MAX_ORACLE_SOURCES,
+        VaultError::InvalidOracleConfig
+    );
+    let min_sources = match params.aggregation {
+        OracleAggregation::Fallback => 1,
+        OracleAggregation::Median => params.min_sources,
+    };
+    require!(
+        min_sources > 0 && min_sources as usize <= num_sources,
+        VaultError::InvalidOracleConfig
+    );
+    for source in params.sources.iter() {
+        match source.provider {
+            // Pyth updates are matched by feed ID, any account may carry them
+            OracleProviderKind::Pyth => {}
+            OracleProviderKind::Switchboard => {
+                require!(source.account != Pubkey::default(), VaultError::InvalidOracleConfig);
+            }
+            OracleProviderKind::Mock => {
+                require!(
+                    cfg!(feature = "mock-oracle") && source.account != Pubkey::default(),
+                    VaultError::InvalidOracleConfig
+                );
+            }
+        }
+    }
+    
+    let feed = &mut ctx.accounts.oracle_feed;
+    feed.bump = ctx.bumps.oracle_feed;
+    feed.feed_id = feed_id;
+    feed.max_staleness_secs = max_staleness_secs;
+    feed.require_full_verification = params.require_full_verification;
+    feed.aggregation = params.aggregation;
+    feed.min_sources = min_sources;
+    feed.num_sources = num_sources as u8;
+    feed.sources = [OracleSource::default(); MAX_ORACLE_SOURCES];
+    feed.sources[..num_sources].copy_from_slice(&params.sources);
+    
+    emit!(OracleFeedUpdatedEvent {
+        feed_id,
+        max_staleness_secs: feed.max_staleness_secs,
+        require_full_verification: feed.require_full_verification,
+        aggregation: feed.aggregation,
+        min_sources: feed.min_sources,
+        sources: params.sources,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Oracle feed updated");
+    msg!("Sources: {}, aggregation: {:?}", feed.num_sources, feed.aggregation);
+    msg!("Max staleness: {}s", feed.max_staleness_secs);
+    msg!("Require full verification: {}", feed.require_full_verification);
     
     Ok(())
 }
@@ -278,6 +675,41 @@ pub struct UpdateConfigParams {
     pub rebalance_threshold_bps: Option<u16>,
     pub max_slippage_bps: Option<u16>,
     pub deposit_cap: Option<u64>,
+    pub max_nav_staleness_slots: Option<u64>,
+    pub max_rate_change_bps_per_epoch: Option<u16>,
+    pub max_confidence_bps: Option<u16>,
+    pub lst_payout_fee_bps: Option<u16>,
+    pub settlement_policy: Option<SettlementPolicy>,
+}
+
+/// Parameters for registering a protocol
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RegisterProtocolParams {
+    pub adapter: AdapterKind,
+    pub unstake_delay: i64,
+    pub supports_instant_unstake: bool,
+    pub instant_unstake_fee_bps: u16,
+    pub lst_deposit_haircut_bps: u16,
+}
+
+/// Parameters for updating a protocol registry entry
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateProtocolParams {
+    pub unstake_delay: Option<i64>,
+    pub supports_instant_unstake: Option<bool>,
+    pub instant_unstake_fee_bps: Option<u16>,
+    pub is_active: Option<bool>,
+    pub lst_deposit_haircut_bps: Option<u16>,
+}
+
+/// Parameters for configuring an oracle feed
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetOracleFeedParams {
+    pub max_staleness_secs: i64,
+    pub require_full_verification: bool,
+    pub aggregation: OracleAggregation,
+    pub min_sources: u8,
+    pub sources: Vec<OracleSource>,
 }
 
 /// Parameters for updating an allocation
@@ -302,6 +734,131 @@ pub struct AdminAction<'info> {
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct MigrateVault<'info> {
+    /// The vault state account, still in the legacy layout
+    /// CHECK: Layout, discriminator and authority are checked in the handler
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump,
+        owner = crate::ID @ VaultError::InvalidAccountOwner
+    )]
+    pub vault: UncheckedAccount<'info>,
+    
+    /// The vault authority, paying for the larger account
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(protocol: u8)]
+pub struct RegisterProtocol<'info> {
+    /// The vault state account
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, VaultState>,
+    
+    /// Registry entry to create
+    #[account(
+        init,
+        payer = authority,
+        space = ProtocolConfig::LEN,
+        seeds = [PROTOCOL_CONFIG_SEED, &[protocol]],
+        bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// Stake pool account (SPL) or state account (Marinade)
+    /// CHECK: Decoded and validated against the adapter kind in the handler
+    pub pool: UncheckedAccount<'info>,
+    
+    /// LST mint
+    pub mint: Account<'info, Mint>,
+    
+    /// Vault-owned custody token account for the LST, one per mint
+    #[account(
+        init,
+        payer = authority,
+        seeds = [CUSTODY_SEED, mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    
+    /// Protocol program
+    /// CHECK: Must be executable and own the pool account
+    pub protocol_program: UncheckedAccount<'info>,
+    
+    /// The vault authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// System program
+    pub system_program: Program<'info, System>,
+    
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocol<'info> {
+    /// The vault state account
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, VaultState>,
+    
+    /// Registry entry to update
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED, &[protocol_config.protocol]],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// The vault authority
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct SetOracleFeed<'info> {
+    /// The vault state account
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, VaultState>,
+    
+    /// Feed configuration to create or update
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = OracleFeedConfig::LEN,
+        seeds = [ORACLE_FEED_SEED, feed_id.as_ref()],
+        bump
+    )]
+    pub oracle_feed: Account<'info, OracleFeedConfig>,
+    
+    /// The vault authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct CollectFees<'info> {
     /// The vault state account
@@ -338,3 +895,62 @@ pub struct CollectFees<'info> {
     /// System program
     pub system_program: Program<'info, System>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn update(protocol: u8, target_bps: u16) -> UpdateAllocation {
+        UpdateAllocation { protocol, target_bps }
+    }
+    
+    #[test]
+    fn test_allocations_follow_protocol() {
+        let mut vault = test_vault(&[(0, 1_000), (1, 2_000)]);
+        vault.allocations[1].current_bps = 6_667;
+        vault.allocations[1].last_rate = 1_050_000_000;
+        vault.allocations[1].last_rate_slot = 42;
+        vault.allocations[1].is_quarantined = true;
+        vault.last_nav_slot = 100;
+        
+        // Protocol 1 moves to the first slot, protocol 2 is new
+        apply_allocations(&mut vault, &[update(1, 5_000), update(0, 3_000), update(2, 2_000)]).unwrap();
+        assert_eq!(vault.num_allocations, 3);
+        
+        let moved = &vault.allocations[0];
+        assert_eq!(moved.protocol, 1);
+        assert_eq!(moved.target_bps, 5_000);
+        assert_eq!(moved.amount, 2_000);
+        assert_eq!(moved.current_bps, 6_667);
+        assert_eq!(moved.last_rate, 1_050_000_000);
+        assert_eq!(moved.last_rate_slot, 42);
+        assert!(moved.is_quarantined);
+        
+        assert_eq!(vault.allocations[1].protocol, 0);
+        assert_eq!(vault.allocations[1].amount, 1_000);
+        assert!(!vault.allocations[1].is_quarantined);
+        
+        let added = &vault.allocations[2];
+        assert_eq!(added.protocol, 2);
+        assert_eq!(added.amount, 0);
+        assert_eq!(added.last_rate, 0);
+        
+        // NAV must be refreshed before shares are priced again
+        assert_eq!(vault.last_nav_slot, 0);
+    }
+    
+    #[test]
+    fn test_drop_funded_protocol_rejected() {
+        let mut vault = test_vault(&[(0, 1_000), (1, 0)]);
+        assert_eq!(
+            apply_allocations(&mut vault, &[update(1, 5_000), update(2, 5_000)]).unwrap_err(),
+            VaultError::AllocationNotEmpty.into()
+        );
+        
+        // An empty protocol can be dropped
+        apply_allocations(&mut vault, &[update(0, 5_000), update(2, 5_000)]).unwrap();
+        assert_eq!(vault.num_allocations, 2);
+        assert_eq!(vault.allocations[0].amount, 1_000);
+        assert_eq!(vault.allocations[1].protocol, 2);
+    }
+}
@@ -7,52 +7,38 @@ use crate::constants::*;
 use crate::errors::VaultError;
 use crate::state::*;
 
-use crate::utils::oracle::{get_pyth_price, pyth_feeds};
+use crate::utils::oracle::{get_validated_price, pyth_feeds};
+use crate::utils::validation::{validate_deposit_amount, validate_deposit_cap};
 
 /// Deposit SOL into the vault and receive jSOLi shares
 /// 
 /// # Arguments
 /// * `ctx` - The context containing all accounts
 /// * `amount` - Amount of lamports to deposit
-pub fn deposit_handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+pub fn deposit_handler<'info>(ctx: Context<'_, '_, 'info, 'info, Deposit<'info>>, amount: u64) -> Result<()> {
     // Validate amount
-    require!(amount > 0, VaultError::ZeroAmount);
-    require!(amount >= MIN_DEPOSIT_LAMPORTS, VaultError::DepositBelowMinimum);
+    validate_deposit_amount(amount)?;
 
     // Validate Oracle Price (Solana / USD)
-    // We check this BEFORE deposit to ensure external market conditions are valid
-    let sol_price = get_pyth_price(&ctx.accounts.oracle_account, MAX_ORACLE_STALENESS_SECS)?;
-    
-    // Verify we are looking at the correct feed
-    require!(
-        ctx.accounts.oracle_account.key().to_string() == pyth_feeds::SOL_USD, 
-        VaultError::InvalidOracleAccount
-    );
-
-    msg!("Oracle Price: ${} (conf: {})", sol_price.price, sol_price.confidence);
+    // We check this BEFORE deposit to ensure external market conditions are valid,
+    // refusing deposits while the market is too uncertain. `oracle_account`
+    // feeds the primary source, `remaining_accounts` the fallback sources
+    get_validated_price(
+        &ctx.accounts.oracle_feed,
+        &ctx.accounts.oracle_account.to_account_info(),
+        ctx.remaining_accounts,
+        ctx.accounts.vault.config.max_confidence_bps,
+        Clock::get()?.unix_timestamp,
+    )?;
 
-    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+    let vault = &ctx.accounts.vault;
     
     // Check vault is not paused
     require!(!vault.config.is_paused, VaultError::VaultPaused);
     
-    // Check deposit cap
-    if vault.config.deposit_cap > 0 {
-        let new_tvl = vault.total_tvl
-            .checked_add(amount)
-            .ok_or(VaultError::MathOverflow)?;
-        require!(
-            new_tvl <= vault.config.deposit_cap,
-            VaultError::DepositCapExceeded
-        );
-    }
-    
-    // Calculate shares to mint
-    let shares_to_mint = vault.calculate_shares_to_mint(amount)?;
-    require!(shares_to_mint > 0, VaultError::ZeroAmount);
-    
-    // Record share price before deposit
-    let share_price = vault.share_price()?;
+    // Shares are priced from total_tvl, so it must reflect current holdings
+    vault.require_fresh_nav(clock.slot)?;
     
     // Transfer SOL from user to vault
     let cpi_context = CpiContext::new(
@@ -64,15 +50,94 @@ pub fn deposit_handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
     );
     anchor_lang::system_program::transfer(cpi_context, amount)?;
     
+    // Mint jSOLi shares to user
+    let user_info = ctx.accounts.user.to_account_info();
+    let jsoli_mint_info = ctx.accounts.jsoli_mint.to_account_info();
+    let user_jsol_info = ctx.accounts.user_jsol_account.to_account_info();
+    let token_program_info = ctx.accounts.token_program.to_account_info();
+    credit_sol_deposit(
+        DepositAccounts {
+            vault: &mut ctx.accounts.vault,
+            user_account: &mut ctx.accounts.user_account,
+            user_account_bump: ctx.bumps.user_account,
+            user: &user_info,
+            jsoli_mint: &jsoli_mint_info,
+            user_jsol_account: &user_jsol_info,
+            token_program: &token_program_info,
+        },
+        amount,
+        clock.unix_timestamp,
+    )?;
+    
+    Ok(())
+}
+
+/// Accounts credited when a deposit is turned into shares
+pub struct DepositAccounts<'a, 'info> {
+    /// The vault state account
+    pub vault: &'a mut Account<'info, VaultState>,
+    /// Depositor's position, initialized on the first deposit
+    pub user_account: &'a mut Account<'info, UserAccount>,
+    /// Bump of the user account PDA
+    pub user_account_bump: u8,
+    /// The depositor
+    pub user: &'a AccountInfo<'info>,
+    /// The jSOLi token mint
+    pub jsoli_mint: &'a AccountInfo<'info>,
+    /// Depositor's jSOLi token account
+    pub user_jsol_account: &'a AccountInfo<'info>,
+    /// Token program
+    pub token_program: &'a AccountInfo<'info>,
+}
+
+/// Shares minted for a deposit
+pub struct DepositCredit {
+    /// jSOLi shares minted to the depositor
+    pub shares_minted: u64,
+    /// Share price before the deposit
+    pub share_price: u64,
+}
+
+/// Quote the shares minted for a deposit
+/// 
+/// `value` is what the deposit adds to TVL and is checked against the
+/// deposit cap. Shares are minted for `credited_value`, which is below
+/// `value` when a haircut applies.
+pub fn quote_deposit_shares(vault: &VaultState, value: u64, credited_value: u64) -> Result<u64> {
+    validate_deposit_cap(vault.total_tvl, value, vault.config.deposit_cap)?;
+    
+    let shares_to_mint = vault.calculate_shares_to_mint(credited_value)?;
+    require!(shares_to_mint > 0, VaultError::ZeroAmount);
+    
+    Ok(shares_to_mint)
+}
+
+/// Mint shares for a deposit the vault has already received
+/// 
+/// Quotes the shares, mints them to the depositor, adds `value` to TVL and
+/// credits `credited_value` to the user's position, initializing it on a
+/// first deposit. Shared by every deposit instruction.
+pub fn credit_deposit(
+    accounts: DepositAccounts,
+    value: u64,
+    credited_value: u64,
+    now: i64,
+) -> Result<DepositCredit> {
+    let vault = accounts.vault;
+    let shares_to_mint = quote_deposit_shares(vault, value, credited_value)?;
+    
+    // Record share price before deposit
+    let share_price = vault.share_price()?;
+    
     // Mint jSOLi shares to user
     let vault_seeds = &[VAULT_SEED, &[vault.bump]];
     let signer_seeds = &[&vault_seeds[..]];
     
     let mint_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
+        accounts.token_program.clone(),
         MintTo {
-            mint: ctx.accounts.jsoli_mint.to_account_info(),
-            to: ctx.accounts.user_jsol_account.to_account_info(),
+            mint: accounts.jsoli_mint.clone(),
+            to: accounts.user_jsol_account.clone(),
             authority: vault.to_account_info(),
         },
         signer_seeds,
@@ -81,21 +146,20 @@ pub fn deposit_handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
     
     // Update vault state
     vault.total_tvl = vault.total_tvl
-        .checked_add(amount)
+        .checked_add(value)
         .ok_or(VaultError::MathOverflow)?;
     vault.total_shares = vault.total_shares
         .checked_add(shares_to_mint)
         .ok_or(VaultError::MathOverflow)?;
     
     // Update or initialize user account
-    let user_account = &mut ctx.accounts.user_account;
-    let clock = Clock::get()?;
+    let user_account = accounts.user_account;
     
     if user_account.first_deposit == 0 {
         // New user
-        user_account.bump = ctx.bumps.user_account;
-        user_account.owner = ctx.accounts.user.key();
-        user_account.first_deposit = clock.unix_timestamp;
+        user_account.bump = accounts.user_account_bump;
+        user_account.owner = accounts.user.key();
+        user_account.first_deposit = now;
         vault.depositor_count = vault.depositor_count
             .checked_add(1)
             .ok_or(VaultError::MathOverflow)?;
@@ -105,23 +169,37 @@ pub fn deposit_handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
         .checked_add(shares_to_mint)
         .ok_or(VaultError::MathOverflow)?;
     user_account.total_deposited = user_account.total_deposited
-        .checked_add(amount)
+        .checked_add(credited_value)
         .ok_or(VaultError::MathOverflow)?;
-    user_account.last_activity = clock.unix_timestamp;
+    user_account.last_activity = now;
+    
+    Ok(DepositCredit {
+        shares_minted: shares_to_mint,
+        share_price,
+    })
+}
+
+/// Mint shares for `amount` lamports of SOL received by the vault
+/// 
+/// Used by the native SOL and wSOL deposit instructions, which emit the
+/// same `DepositEvent`.
+pub fn credit_sol_deposit(accounts: DepositAccounts, amount: u64, now: i64) -> Result<()> {
+    let user = accounts.user.key();
+    let credit = credit_deposit(accounts, amount, amount, now)?;
     
     // Emit deposit event
     emit!(DepositEvent {
-        user: ctx.accounts.user.key(),
+        user,
         lamports: amount,
-        shares_minted: shares_to_mint,
-        share_price: share_price,
-        timestamp: Clock::get()?.unix_timestamp,
+        shares_minted: credit.shares_minted,
+        share_price: credit.share_price,
+        timestamp: now,
     });
     
     msg!("Deposit successful");
     msg!("Amount: {} lamports", amount);
-    msg!("Shares minted: {}", shares_to_mint);
-    msg!("Share price: {}", share_price);
+    msg!("Shares minted: {}", credit.shares_minted);
+    msg!("Share price: {}", credit.share_price);
     
     Ok(())
 }
@@ -167,7 +245,7 @@ pub struct Deposit<'info> {
     /// CHECK: This is a PDA that holds SOL
     #[account(
         mut,
-        seeds = [b"vault_sol"],
+        seeds = [VAULT_SOL_SEED],
         bump
     )]
     pub vault_sol_account: UncheckedAccount<'info>,
@@ -187,8 +265,15 @@ pub struct Deposit<'info> {
     
     /// Rent sysvar
     pub rent: Sysvar<'info, Rent>,
-
-    /// Pyth Oracle Account (SOL/USD)
-    /// CHECK: Validated by Pyth SDK and Feed ID check in handler
-    pub oracle_account: AccountInfo<'info>,
+    
+    /// SOL/USD feed configuration
+    #[account(
+        seeds = [ORACLE_FEED_SEED, pyth_feeds::SOL_USD.as_ref()],
+        bump = oracle_feed.bump
+    )]
+    pub oracle_feed: Box<Account<'info, OracleFeedConfig>>,
+    
+    /// Price account for the feed's primary source (SOL/USD)
+    /// CHECK: Owner, layout and feed are verified by the source's provider
+    pub oracle_account: UncheckedAccount<'info>,
 }
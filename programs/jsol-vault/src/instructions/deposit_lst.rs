@@ -0,0 +1,275 @@
+//! LST deposit instruction
+//!
+//! Lets holders of a supported LST join the vault without unstaking or
+//! swapping first.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::VaultError;
+use crate::instructions::deposit::{credit_deposit, DepositAccounts};
+use crate::state::*;
+use crate::utils::adapter::{custody_value, lst_to_lamports};
+use crate::utils::math::apply_haircut;
+use crate::utils::validation::validate_allocation_cap;
+
+/// Deposit a supported LST and receive jSOLi shares
+///
+/// The LST is transferred into the protocol's custody account and valued at
+/// the live exchange rate less the protocol's `lst_deposit_haircut_bps`.
+/// Shares are minted for the value after the haircut, so the haircut
+/// accrues to existing holders. The deposit is rejected if it would push
+/// the protocol's share of TVL past `MAX_PROTOCOL_ALLOCATION_BPS`.
+///
+/// `remaining_accounts` holds the protocol's exchange rate accounts, in the
+/// order documented by its `LstAdapter` implementation.
+///
+/// # Arguments
+/// * `ctx` - The context containing all accounts
+/// * `protocol` - The LST protocol whose token is deposited
+/// * `amount` - Amount of LST to deposit, in token base units
+pub fn deposit_lst_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DepositLst<'info>>,
+    protocol: u8,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, VaultError::ZeroAmount);
+    
+    let clock = Clock::get()?;
+    let vault = &ctx.accounts.vault;
+    let config = &ctx.accounts.protocol_config;
+    
+    // Check vault is not paused
+    require!(!vault.config.is_paused, VaultError::VaultPaused);
+    
+    // Shares are priced from total_tvl, so it must reflect current holdings
+    vault.require_fresh_nav(clock.slot)?;
+    
+    // Validate the protocol
+    let protocol_index = vault.allocations[..vault.num_allocations as usize]
+        .iter()
+        .position(|alloc| alloc.protocol == protocol)
+        .ok_or(VaultError::UnsupportedProtocol)?;
+    require!(config.is_active, VaultError::ProtocolDisabled);
+    require!(
+        !vault.allocations[protocol_index].is_quarantined,
+        VaultError::ProtocolQuarantined
+    );
+    
+    // Value the deposit at the live exchange rate, less the haircut
+    let adapter = config.load_adapter();
+    let exchange_rate = adapter.exchange_rate(ctx.remaining_accounts)?;
+    let haircut_bps = config.lst_deposit_haircut_bps;
+    let (gross_value, credited_value) = value_lst_deposit(amount, exchange_rate, haircut_bps)?;
+    
+    // Check the protocol stays within its allocation cap
+    validate_allocation_cap(
+        vault.allocations[protocol_index].amount,
+        vault.total_tvl,
+        gross_value,
+    )?;
+    
+    // Transfer LST from user to custody
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_lst_account.to_account_info(),
+            to: ctx.accounts.vault_lst_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+    
+    // Mint jSOLi shares to user for the value after the haircut
+    let user_info = ctx.accounts.user.to_account_info();
+    let jsoli_mint_info = ctx.accounts.jsoli_mint.to_account_info();
+    let user_jsol_info = ctx.accounts.user_jsol_account.to_account_info();
+    let token_program_info = ctx.accounts.token_program.to_account_info();
+    let credit = credit_deposit(
+        DepositAccounts {
+            vault: &mut ctx.accounts.vault,
+            user_account: &mut ctx.accounts.user_account,
+            user_account_bump: ctx.bumps.user_account,
+            user: &user_info,
+            jsoli_mint: &jsoli_mint_info,
+            user_jsol_account: &user_jsol_info,
+            token_program: &token_program_info,
+        },
+        gross_value,
+        credited_value,
+        clock.unix_timestamp,
+    )?;
+    
+    // Re-value the allocation from the custody balance
+    ctx.accounts.vault_lst_account.reload()?;
+    let custody = custody_value(
+        adapter.as_ref(),
+        &ctx.accounts.vault_lst_account.to_account_info(),
+        ctx.remaining_accounts,
+    )?;
+    
+    let vault = &mut ctx.accounts.vault;
+    vault.allocations[protocol_index].amount = custody;
+    vault.update_current_bps()?;
+    
+    emit!(LstDepositEvent {
+        user: ctx.accounts.user.key(),
+        protocol,
+        mint: ctx.accounts.protocol_config.mint,
+        lst_amount: amount,
+        exchange_rate,
+        gross_value,
+        credited_value,
+        haircut_bps,
+        shares_minted: credit.shares_minted,
+        share_price: credit.share_price,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("LST deposit successful");
+    msg!("Amount: {} LST worth {} lamports ({} credited)", amount, gross_value, credited_value);
+    msg!("Shares minted: {}", credit.shares_minted);
+    msg!("Share price: {}", credit.share_price);
+    
+    Ok(())
+}
+
+/// Value an LST deposit at `exchange_rate`, less `haircut_bps`
+/// 
+/// Returns the gross value added to TVL and the value credited to the
+/// depositor, which must meet the minimum deposit.
+pub fn value_lst_deposit(amount: u64, exchange_rate: u64, haircut_bps: u16) -> Result<(u64, u64)> {
+    let gross_value = lst_to_lamports(amount, exchange_rate)?;
+    let credited_value = apply_haircut(gross_value, haircut_bps)?;
+    require!(credited_value >= MIN_DEPOSIT_LAMPORTS, VaultError::DepositBelowMinimum);
+    
+    Ok((gross_value, credited_value))
+}
+
+#[derive(Accounts)]
+#[instruction(protocol: u8)]
+pub struct DepositLst<'info> {
+    /// The vault state account
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Box<Account<'info, VaultState>>,
+    
+    /// The jSOLi token mint
+    #[account(
+        mut,
+        seeds = [JSOLI_MINT_SEED],
+        bump
+    )]
+    pub jsoli_mint: Box<Account<'info, Mint>>,
+    
+    /// User's jSOLi token account
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = jsoli_mint,
+        associated_token::authority = user
+    )]
+    pub user_jsol_account: Box<Account<'info, TokenAccount>>,
+    
+    /// User account for tracking position
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserAccount::LEN,
+        seeds = [USER_ACCOUNT_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_account: Box<Account<'info, UserAccount>>,
+    
+    /// Registry entry for the protocol
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED, &[protocol]],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+    
+    /// User's LST token account
+    #[account(
+        mut,
+        token::mint = protocol_config.mint,
+        token::authority = user
+    )]
+    pub user_lst_account: Box<Account<'info, TokenAccount>>,
+    
+    /// Registered vault token account receiving the LST
+    #[account(
+        mut,
+        address = protocol_config.vault_token_account @ VaultError::InvalidProtocolAccount
+    )]
+    pub vault_lst_account: Box<Account<'info, TokenAccount>>,
+    
+    /// The user depositing LST
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// System program
+    pub system_program: Program<'info, System>,
+    
+    /// Token program
+    pub token_program: Program<'info, Token>,
+    
+    /// Associated token program
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    
+    /// Rent sysvar
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::deposit::quote_deposit_shares;
+    
+    const SOL: u64 = 1_000_000_000;
+    
+    #[test]
+    fn test_value_lst_deposit() {
+        // 10 LST at 1.1 SOL each, 1% haircut
+        let (gross, credited) = value_lst_deposit(10 * SOL, 1_100_000_000, 100).unwrap();
+        assert_eq!(gross, 11 * SOL);
+        assert_eq!(credited, 10_890_000_000);
+        
+        // No haircut credits the full value
+        let (gross, credited) = value_lst_deposit(10 * SOL, 1_100_000_000, 0).unwrap();
+        assert_eq!(credited, gross);
+        
+        // The minimum applies to the value after the haircut
+        assert_eq!(
+            value_lst_deposit(MIN_DEPOSIT_LAMPORTS, SHARE_PRECISION, 100).unwrap_err(),
+            VaultError::DepositBelowMinimum.into()
+        );
+    }
+    
+    #[test]
+    fn test_lst_deposit_shares() {
+        // 100 SOL backing 80 shares, i.e. 1.25 SOL per share
+        let mut vault = test_vault(&[]);
+        vault.total_tvl = 100 * SOL;
+        vault.total_shares = 80 * SOL;
+        let (gross, credited) = value_lst_deposit(10 * SOL, 1_100_000_000, 100).unwrap();
+        
+        // Shares are minted for the credited value only
+        let shares = quote_deposit_shares(&vault, gross, credited).unwrap();
+        assert_eq!(shares, 8_712_000_000);
+        assert!(shares < quote_deposit_shares(&vault, gross, gross).unwrap());
+        
+        // The deposit cap is checked against the gross value
+        let mut capped = vault;
+        capped.config.deposit_cap = 111 * SOL;
+        assert!(quote_deposit_shares(&capped, gross, credited).is_ok());
+        capped.config.deposit_cap = 111 * SOL - 1;
+        assert_eq!(
+            quote_deposit_shares(&capped, gross, credited).unwrap_err(),
+            VaultError::DepositCapExceeded.into()
+        );
+    }
+}
@@ -0,0 +1,409 @@
+//! Stake account deposit instruction
+//!
+//! Lets holders of delegated native stake join the vault without
+//! unstaking first.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::stake::{
+    self,
+    state::{StakeAuthorize, StakeStateV2},
+};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::constants::*;
+use crate::errors::VaultError;
+use crate::instructions::deposit::{credit_deposit, DepositAccounts};
+use crate::state::*;
+use crate::utils::adapter::{adapter_deposit_stake, custody_value, VaultAccounts};
+use crate::utils::validation::validate_allocation_cap;
+
+/// Deposit an active stake account and receive jSOLi shares
+///
+/// The stake account must be fully delegated, not deactivating, out of
+/// lockup and have the user as both staker and withdrawer, and the deposit
+/// must keep the protocol within its allocation cap. Both authorities
+/// are moved to the vault PDA, which then deposits the account into the
+/// protocol's stake pool. Shares are minted for the value of the pool
+/// tokens received, i.e. the stake account's lamports less any pool
+/// deposit fee.
+///
+/// Protocol-specific accounts are passed in `remaining_accounts`, in the
+/// order documented by the protocol's `LstAdapter` implementation.
+///
+/// # Arguments
+/// * `ctx` - The context containing all accounts
+/// * `protocol` - The LST protocol whose pool receives the stake
+pub fn deposit_stake_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DepositStake<'info>>,
+    protocol: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault = &ctx.accounts.vault;
+    
+    // Check vault is not paused
+    require!(!vault.config.is_paused, VaultError::VaultPaused);
+    
+    // Shares are priced from total_tvl, so it must reflect current holdings
+    vault.require_fresh_nav(clock.slot)?;
+    
+    // Validate the target protocol
+    let protocol_index = vault.allocations[..vault.num_allocations as usize]
+        .iter()
+        .position(|alloc| alloc.protocol == protocol)
+        .ok_or(VaultError::UnsupportedProtocol)?;
+    require!(ctx.accounts.protocol_config.is_active, VaultError::ProtocolDisabled);
+    require!(
+        !vault.allocations[protocol_index].is_quarantined,
+        VaultError::ProtocolQuarantined
+    );
+    
+    // Validate the stake account's delegation and authorities
+    let user_key = ctx.accounts.user.key();
+    let (voter, active_stake) = {
+        let data = ctx.accounts.stake_account.try_borrow_data()?;
+        let state = StakeStateV2::deserialize(&mut &data[..])
+            .map_err(|_| VaultError::InvalidStakeAccount)?;
+        validate_stake_account(&state, &user_key, &clock)?
+    };
+    
+    // Hand the stake and withdraw authorities to the vault PDA
+    let vault_key = vault.key();
+    for authority_type in [StakeAuthorize::Staker, StakeAuthorize::Withdrawer] {
+        let ix = stake::instruction::authorize(
+            ctx.accounts.stake_account.key,
+            &user_key,
+            &vault_key,
+            authority_type,
+            None,
+        );
+        invoke(
+            &ix,
+            &[
+                ctx.accounts.stake_account.to_account_info(),
+                ctx.accounts.clock.to_account_info(),
+                ctx.accounts.user.to_account_info(),
+            ],
+        )?;
+    }
+    
+    // Deposit the stake into the protocol, then value the allocation from
+    // the custody balance at the live exchange rate
+    let adapter = ctx.accounts.protocol_config.load_adapter();
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault_sol_info = ctx.accounts.vault_sol_account.to_account_info();
+    let vault_token_info = ctx.accounts.vault_lst_account.to_account_info();
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+    let token_program_info = ctx.accounts.token_program.to_account_info();
+    let vault_accounts = VaultAccounts {
+        vault: &vault_info,
+        vault_sol: &vault_sol_info,
+        vault_token_account: &vault_token_info,
+        system_program: &system_program_info,
+        token_program: &token_program_info,
+        vault_bump: ctx.accounts.vault.bump,
+        vault_sol_bump: ctx.bumps.vault_sol_account,
+    };
+    let value = adapter_deposit_stake(
+        adapter.as_ref(),
+        &vault_accounts,
+        &ctx.accounts.stake_account.to_account_info(),
+        ctx.remaining_accounts,
+    )?;
+    let custody = custody_value(adapter.as_ref(), &vault_token_info, ctx.remaining_accounts)?;
+    
+    // Check the protocol stays within its allocation cap
+    let vault = &ctx.accounts.vault;
+    validate_allocation_cap(
+        vault.allocations[protocol_index].amount,
+        vault.total_tvl,
+        value,
+    )?;
+    
+    // Mint jSOLi shares to user
+    let user_info = ctx.accounts.user.to_account_info();
+    let jsoli_mint_info = ctx.accounts.jsoli_mint.to_account_info();
+    let user_jsol_info = ctx.accounts.user_jsol_account.to_account_info();
+    let credit = credit_deposit(
+        DepositAccounts {
+            vault: &mut ctx.accounts.vault,
+            user_account: &mut ctx.accounts.user_account,
+            user_account_bump: ctx.bumps.user_account,
+            user: &user_info,
+            jsoli_mint: &jsoli_mint_info,
+            user_jsol_account: &user_jsol_info,
+            token_program: &token_program_info,
+        },
+        value,
+        value,
+        clock.unix_timestamp,
+    )?;
+    
+    let vault = &mut ctx.accounts.vault;
+    vault.allocations[protocol_index].amount = custody;
+    vault.update_current_bps()?;
+    
+    emit!(StakeDepositEvent {
+        user: user_key,
+        stake_account: ctx.accounts.stake_account.key(),
+        voter,
+        protocol,
+        active_stake,
+        lamports: value,
+        shares_minted: credit.shares_minted,
+        share_price: credit.share_price,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Stake deposit successful");
+    msg!("Active stake: {} lamports, valued at {}", active_stake, value);
+    msg!("Shares minted: {}", credit.shares_minted);
+    msg!("Share price: {}", credit.share_price);
+    
+    Ok(())
+}
+
+/// Check a stake account can be deposited by `user`
+/// 
+/// The account must be delegated since before the current epoch and not
+/// deactivating, with `user` as both staker and withdrawer and no lockup in
+/// force. Returns the delegation's vote account and active stake.
+pub fn validate_stake_account(
+    state: &StakeStateV2,
+    user: &Pubkey,
+    clock: &Clock,
+) -> Result<(Pubkey, u64)> {
+    let StakeStateV2::Stake(meta, stake, _) = state else {
+        return err!(VaultError::InvalidStakeAccount);
+    };
+    
+    require_keys_eq!(meta.authorized.staker, *user, VaultError::InvalidStakeAccount);
+    require_keys_eq!(meta.authorized.withdrawer, *user, VaultError::InvalidStakeAccount);
+    require!(!meta.lockup.is_in_force(clock, None), VaultError::StakeAccountLocked);
+    require!(
+        stake.delegation.deactivation_epoch == u64::MAX
+            && stake.delegation.activation_epoch < clock.epoch,
+        VaultError::InvalidStakeAccount
+    );
+    
+    Ok((stake.delegation.voter_pubkey, stake.delegation.stake))
+}
+
+#[derive(Accounts)]
+#[instruction(protocol: u8)]
+pub struct DepositStake<'info> {
+    /// The vault state account
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Box<Account<'info, VaultState>>,
+    
+    /// The jSOLi token mint
+    #[account(
+        mut,
+        seeds = [JSOLI_MINT_SEED],
+        bump
+    )]
+    pub jsoli_mint: Box<Account<'info, Mint>>,
+    
+    /// User's jSOLi token account
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = jsoli_mint,
+        associated_token::authority = user
+    )]
+    pub user_jsol_account: Box<Account<'info, TokenAccount>>,
+    
+    /// User account for tracking position
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserAccount::LEN,
+        seeds = [USER_ACCOUNT_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_account: Box<Account<'info, UserAccount>>,
+    
+    /// Stake account being deposited
+    /// CHECK: Owner is checked here, delegation and authorities in the handler
+    #[account(
+        mut,
+        owner = stake::program::ID @ VaultError::InvalidStakeAccount
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+    
+    /// Registry entry for the protocol
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED, &[protocol]],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+    
+    /// Registered vault token account receiving the LST
+    #[account(
+        mut,
+        address = protocol_config.vault_token_account @ VaultError::InvalidProtocolAccount
+    )]
+    pub vault_lst_account: Box<Account<'info, TokenAccount>>,
+    
+    /// Vault's SOL holding account
+    /// CHECK: This is a PDA that holds SOL
+    #[account(
+        seeds = [VAULT_SOL_SEED],
+        bump
+    )]
+    pub vault_sol_account: UncheckedAccount<'info>,
+    
+    /// The user depositing stake, current staker and withdrawer
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Clock sysvar
+    pub clock: Sysvar<'info, Clock>,
+    
+    /// System program
+    pub system_program: Program<'info, System>,
+    
+    /// Token program
+    pub token_program: Program<'info, Token>,
+    
+    /// Associated token program
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    
+    /// Rent sysvar
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::stake::stake_flags::StakeFlags;
+    use anchor_lang::solana_program::stake::state::{Authorized, Delegation, Lockup, Meta, Stake};
+    
+    const EPOCH: u64 = 500;
+    
+    fn clock() -> Clock {
+        Clock {
+            slot: 0,
+            epoch_start_timestamp: 1_700_000_000,
+            epoch: EPOCH,
+            leader_schedule_epoch: EPOCH + 1,
+            unix_timestamp: 1_700_000_000,
+        }
+    }
+    
+    fn stake_state(user: &Pubkey, voter: &Pubkey, activation_epoch: u64) -> StakeStateV2 {
+        let meta = Meta {
+            rent_exempt_reserve: 2_282_880,
+            authorized: Authorized::auto(user),
+            lockup: Lockup::default(),
+        };
+        let stake = Stake {
+            delegation: Delegation::new(voter, 10_000_000_000, activation_epoch),
+            credits_observed: 0,
+        };
+        StakeStateV2::Stake(meta, stake, StakeFlags::empty())
+    }
+    
+    fn with_meta(state: StakeStateV2, update: impl FnOnce(&mut Meta)) -> StakeStateV2 {
+        let StakeStateV2::Stake(mut meta, stake, flags) = state else {
+            unreachable!()
+        };
+        update(&mut meta);
+        StakeStateV2::Stake(meta, stake, flags)
+    }
+    
+    #[test]
+    fn test_validate_active_delegation() {
+        let user = Pubkey::new_unique();
+        let voter = Pubkey::new_unique();
+        let state = stake_state(&user, &voter, EPOCH - 1);
+        assert_eq!(
+            validate_stake_account(&state, &user, &clock()).unwrap(),
+            (voter, 10_000_000_000)
+        );
+        
+        // Stake activating this epoch is not yet fully delegated
+        let activating = stake_state(&user, &voter, EPOCH);
+        assert_eq!(
+            validate_stake_account(&activating, &user, &clock()).unwrap_err(),
+            VaultError::InvalidStakeAccount.into()
+        );
+        
+        // Undelegated accounts are rejected
+        let meta = Meta {
+            authorized: Authorized::auto(&user),
+            ..Meta::default()
+        };
+        assert_eq!(
+            validate_stake_account(&StakeStateV2::Initialized(meta), &user, &clock()).unwrap_err(),
+            VaultError::InvalidStakeAccount.into()
+        );
+    }
+    
+    #[test]
+    fn test_validate_deactivating_stake_rejected() {
+        let user = Pubkey::new_unique();
+        let StakeStateV2::Stake(meta, mut stake, flags) = stake_state(&user, &Pubkey::new_unique(), 1) else {
+            unreachable!()
+        };
+        stake.delegation.deactivation_epoch = EPOCH;
+        assert_eq!(
+            validate_stake_account(&StakeStateV2::Stake(meta, stake, flags), &user, &clock()).unwrap_err(),
+            VaultError::InvalidStakeAccount.into()
+        );
+    }
+    
+    #[test]
+    fn test_validate_stake_authorities() {
+        let user = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let state = stake_state(&user, &Pubkey::new_unique(), 1);
+        
+        // Only the account's own staker and withdrawer can deposit it
+        assert_eq!(
+            validate_stake_account(&state, &other, &clock()).unwrap_err(),
+            VaultError::InvalidStakeAccount.into()
+        );
+        
+        let staker_only = with_meta(state, |meta| meta.authorized.withdrawer = other);
+        assert_eq!(
+            validate_stake_account(&staker_only, &user, &clock()).unwrap_err(),
+            VaultError::InvalidStakeAccount.into()
+        );
+        
+        let withdrawer_only = with_meta(state, |meta| {
+            meta.authorized.staker = other;
+            meta.authorized.withdrawer = user;
+        });
+        assert_eq!(
+            validate_stake_account(&withdrawer_only, &user, &clock()).unwrap_err(),
+            VaultError::InvalidStakeAccount.into()
+        );
+    }
+    
+    #[test]
+    fn test_validate_stake_lockup() {
+        let user = Pubkey::new_unique();
+        let state = stake_state(&user, &Pubkey::new_unique(), 1);
+        
+        let locked = with_meta(state, |meta| meta.lockup.epoch = EPOCH + 1);
+        assert_eq!(
+            validate_stake_account(&locked, &user, &clock()).unwrap_err(),
+            VaultError::StakeAccountLocked.into()
+        );
+        
+        let locked_until = with_meta(state, |meta| meta.lockup.unix_timestamp = 1_800_000_000);
+        assert_eq!(
+            validate_stake_account(&locked_until, &user, &clock()).unwrap_err(),
+            VaultError::StakeAccountLocked.into()
+        );
+        
+        // An expired lockup no longer applies
+        let expired = with_meta(state, |meta| meta.lockup.epoch = EPOCH);
+        assert!(validate_stake_account(&expired, &user, &clock()).is_ok());
+    }
+}
@@ -0,0 +1,212 @@
+//! Wrapped SOL deposit instruction
+//!
+//! Lets integrations that hold SOL in SPL token accounts deposit without
+//! unwrapping first.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, spl_token, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::VaultError;
+use crate::state::*;
+use crate::instructions::deposit::{credit_sol_deposit, DepositAccounts};
+use crate::utils::oracle::{get_validated_price, pyth_feeds};
+use crate::utils::validation::validate_deposit_amount;
+
+/// Deposit wSOL into the vault and receive jSOLi shares
+///
+/// The wSOL is moved into a temporary vault-owned token account, which is
+/// closed into the vault SOL PDA to unwrap it. The temporary account's rent
+/// is returned to the user. Oracle checks are the same as for a native
+/// deposit, and shares are credited through the same `credit_sol_deposit`.
+///
+/// # Arguments
+/// * `ctx` - The context containing all accounts
+/// * `amount` - Amount of wSOL to deposit, in lamports
+pub fn deposit_wsol_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DepositWsol<'info>>,
+    amount: u64,
+) -> Result<()> {
+    // Validate amount
+    validate_deposit_amount(amount)?;
+    
+    // Validate Oracle Price (Solana / USD), as for native deposits
+    get_validated_price(
+        &ctx.accounts.oracle_feed,
+        &ctx.accounts.oracle_account.to_account_info(),
+        ctx.remaining_accounts,
+        ctx.accounts.vault.config.max_confidence_bps,
+        Clock::get()?.unix_timestamp,
+    )?;
+    
+    let clock = Clock::get()?;
+    let vault = &ctx.accounts.vault;
+    
+    // Check vault is not paused
+    require!(!vault.config.is_paused, VaultError::VaultPaused);
+    
+    // Shares are priced from total_tvl, so it must reflect current holdings
+    vault.require_fresh_nav(clock.slot)?;
+    
+    // Move the wSOL into the temporary account
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_wsol_account.to_account_info(),
+            to: ctx.accounts.wsol_temp_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+    
+    // Unwrap by closing the temporary account into the vault SOL PDA
+    let vault_seeds = &[VAULT_SEED, &[vault.bump]];
+    let signer_seeds = &[&vault_seeds[..]];
+    
+    let rent_lamports = ctx.accounts.wsol_temp_account
+        .to_account_info()
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(VaultError::MathUnderflow)?;
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.wsol_temp_account.to_account_info(),
+            destination: ctx.accounts.vault_sol_account.to_account_info(),
+            authority: vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::close_account(close_ctx)?;
+    
+    // Return the temporary account's rent to the user
+    let vault_sol_seeds = &[VAULT_SOL_SEED, &[ctx.bumps.vault_sol_account]];
+    let vault_sol_signer = &[&vault_sol_seeds[..]];
+    let refund_ctx = CpiContext::new_with_signer(
+        ctx.accounts.system_program.to_account_info(),
+        anchor_lang::system_program::Transfer {
+            from: ctx.accounts.vault_sol_account.to_account_info(),
+            to: ctx.accounts.user.to_account_info(),
+        },
+        vault_sol_signer,
+    );
+    anchor_lang::system_program::transfer(refund_ctx, rent_lamports)?;
+    
+    // Mint jSOLi shares to user
+    let user_info = ctx.accounts.user.to_account_info();
+    let jsoli_mint_info = ctx.accounts.jsoli_mint.to_account_info();
+    let user_jsol_info = ctx.accounts.user_jsol_account.to_account_info();
+    let token_program_info = ctx.accounts.token_program.to_account_info();
+    credit_sol_deposit(
+        DepositAccounts {
+            vault: &mut ctx.accounts.vault,
+            user_account: &mut ctx.accounts.user_account,
+            user_account_bump: ctx.bumps.user_account,
+            user: &user_info,
+            jsoli_mint: &jsoli_mint_info,
+            user_jsol_account: &user_jsol_info,
+            token_program: &token_program_info,
+        },
+        amount,
+        clock.unix_timestamp,
+    )?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DepositWsol<'info> {
+    /// The vault state account
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Box<Account<'info, VaultState>>,
+    
+    /// The jSOLi token mint
+    #[account(
+        mut,
+        seeds = [JSOLI_MINT_SEED],
+        bump
+    )]
+    pub jsoli_mint: Box<Account<'info, Mint>>,
+    
+    /// User's jSOLi token account
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = jsoli_mint,
+        associated_token::authority = user
+    )]
+    pub user_jsol_account: Box<Account<'info, TokenAccount>>,
+    
+    /// User account for tracking position
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserAccount::LEN,
+        seeds = [USER_ACCOUNT_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_account: Box<Account<'info, UserAccount>>,
+    
+    /// Vault's SOL holding account
+    /// CHECK: This is a PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [VAULT_SOL_SEED],
+        bump
+    )]
+    pub vault_sol_account: UncheckedAccount<'info>,
+    
+    /// Native mint
+    #[account(address = spl_token::native_mint::ID @ VaultError::InvalidMint)]
+    pub wsol_mint: Box<Account<'info, Mint>>,
+    
+    /// User's wSOL token account
+    #[account(
+        mut,
+        token::mint = wsol_mint,
+        token::authority = user
+    )]
+    pub user_wsol_account: Box<Account<'info, TokenAccount>>,
+    
+    /// Temporary vault-owned wSOL account, closed within the instruction
+    #[account(
+        init,
+        payer = user,
+        seeds = [WSOL_TEMP_SEED, user.key().as_ref()],
+        bump,
+        token::mint = wsol_mint,
+        token::authority = vault
+    )]
+    pub wsol_temp_account: Box<Account<'info, TokenAccount>>,
+    
+    /// The user depositing wSOL
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// System program
+    pub system_program: Program<'info, System>,
+    
+    /// Token program
+    pub token_program: Program<'info, Token>,
+    
+    /// Associated token program
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    
+    /// Rent sysvar
+    pub rent: Sysvar<'info, Rent>,
+    
+    /// SOL/USD feed configuration
+    #[account(
+        seeds = [ORACLE_FEED_SEED, pyth_feeds::SOL_USD.as_ref()],
+        bump = oracle_feed.bump
+    )]
+    pub oracle_feed: Box<Account<'info, OracleFeedConfig>>,
+    
+    /// Price account for the feed's primary source (SOL/USD)
+    /// CHECK: Owner, layout and feed are verified by the source's provider
+    pub oracle_account: UncheckedAccount<'info>,
+}
@@ -86,6 +86,11 @@ pub fn initialize_handler(
         max_slippage_bps: 100,
         deposit_cap,
         is_paused: false,
+        max_nav_staleness_slots: DEFAULT_MAX_NAV_STALENESS_SLOTS,
+        max_rate_change_bps_per_epoch: DEFAULT_MAX_RATE_CHANGE_BPS_PER_EPOCH,
+        max_confidence_bps: DEFAULT_MAX_CONFIDENCE_BPS,
+        lst_payout_fee_bps: DEFAULT_LST_PAYOUT_FEE_BPS,
+        settlement_policy: SettlementPolicy::default(),
     };
     
     // Set allocations
@@ -96,6 +101,10 @@ pub fn initialize_handler(
             target_bps: alloc.target_bps,
             current_bps: 0, // No current allocation yet
             amount: 0,
+            last_rate: 0,
+            last_rate_slot: 0,
+            last_rate_epoch: 0,
+            is_quarantined: false,
         };
     }
     
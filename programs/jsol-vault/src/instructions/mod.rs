@@ -2,14 +2,26 @@
 
 pub mod initialize;
 pub mod deposit;
+pub mod deposit_stake;
+pub mod deposit_lst;
+pub mod deposit_wsol;
 pub mod withdraw;
+pub mod redeem;
+pub mod withdraw_stake;
 pub mod rebalance;
 pub mod admin;
 pub mod staking;
+pub mod nav;
 
 pub use initialize::*;
 pub use deposit::*;
+pub use deposit_stake::*;
+pub use deposit_lst::*;
+pub use deposit_wsol::*;
 pub use withdraw::*;
+pub use redeem::*;
+pub use withdraw_stake::*;
 pub use rebalance::*;
 pub use admin::*;
 pub use staking::*;
+pub use nav::*;
@@ -0,0 +1,148 @@
+//! NAV refresh instruction
+//!
+//! Recomputes `total_tvl` from the vault's actual holdings so the share
+//! price reflects staking yield.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+use crate::constants::*;
+use crate::errors::VaultError;
+use crate::state::*;
+use crate::utils::adapter::lst_to_lamports;
+use crate::utils::oracle::validate_price_deviation;
+
+/// Refresh the vault NAV from its holdings
+///
+/// Sums idle SOL in the vault SOL PDA, lamports owed by open delayed-unstake
+/// tickets and each protocol's custody balance at its live exchange rate,
+/// then writes the result into `total_tvl` and the per-allocation amounts.
+/// Can be called by anyone.
+///
+/// Each rate is checked against the allocation's last accepted rate. A
+/// protocol whose rate moved more than `max_rate_change_bps_per_epoch` per
+/// elapsed epoch is quarantined: it keeps its stored rate, is valued at the
+/// lower of the two rates and takes no new stake until the authority
+/// releases it.
+///
+/// `remaining_accounts` holds one group per allocation, in allocation order:
+/// `[protocol_config, vault_token_account, pool/state account]`.
+///
+/// # Arguments
+/// * `ctx` - The context containing all accounts
+pub fn update_nav_handler<'info>(ctx: Context<'_, '_, 'info, 'info, UpdateNav<'info>>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+    
+    let idle_lamports = ctx.accounts.vault_sol_account.lamports();
+    let mut new_tvl = idle_lamports
+        .checked_add(vault.pending_unstake_lamports)
+        .ok_or(VaultError::MathOverflow)?;
+    let mut allocation_values = [0u64; MAX_PROTOCOLS];
+    
+    let num_allocations = vault.num_allocations as usize;
+    require!(
+        ctx.remaining_accounts.len() >= num_allocations * 3,
+        VaultError::MissingProtocolAccounts
+    );
+    
+    for (i, group) in ctx.remaining_accounts.chunks_exact(3).take(num_allocations).enumerate() {
+        let config = Account::<ProtocolConfig>::try_from(&group[0])?;
+        require!(
+            config.protocol == vault.allocations[i].protocol,
+            VaultError::InvalidProtocolAccount
+        );
+        require_keys_eq!(
+            group[1].key(),
+            config.vault_token_account,
+            VaultError::InvalidProtocolAccount
+        );
+        
+        let adapter = config.load_adapter();
+        let rate = adapter.exchange_rate(&group[2..])?;
+        let max_change_bps = vault.config.max_rate_change_bps_per_epoch;
+        let alloc = &mut vault.allocations[i];
+        
+        // Guard against exchange rate jumps since the last accepted rate
+        if !alloc.is_quarantined && alloc.last_rate > 0 && max_change_bps > 0 {
+            let epochs = clock.epoch.saturating_sub(alloc.last_rate_epoch).max(1);
+            let allowed_bps = (max_change_bps as u64)
+                .saturating_mul(epochs)
+                .min(u16::MAX as u64) as u16;
+            
+            if validate_price_deviation(rate, alloc.last_rate, allowed_bps).is_err() {
+                alloc.is_quarantined = true;
+                
+                emit!(RateQuarantinedEvent {
+                    protocol: alloc.protocol,
+                    last_rate: alloc.last_rate,
+                    new_rate: rate,
+                    last_rate_epoch: alloc.last_rate_epoch,
+                    epoch: clock.epoch,
+                    timestamp: clock.unix_timestamp,
+                });
+                
+                msg!("Protocol {} quarantined: rate {} -> {}", alloc.protocol, alloc.last_rate, rate);
+            }
+        }
+        
+        let valuation_rate = if alloc.is_quarantined {
+            rate.min(alloc.last_rate)
+        } else {
+            alloc.last_rate = rate;
+            alloc.last_rate_slot = clock.slot;
+            alloc.last_rate_epoch = clock.epoch;
+            rate
+        };
+        
+        let value = lst_to_lamports(accessor::amount(&group[1])?, valuation_rate)?;
+        alloc.amount = value;
+        allocation_values[i] = value;
+        new_tvl = new_tvl
+            .checked_add(value)
+            .ok_or(VaultError::MathOverflow)?;
+    }
+    
+    let old_tvl = vault.total_tvl;
+    vault.total_tvl = new_tvl;
+    vault.update_current_bps()?;
+    vault.last_nav_slot = clock.slot;
+    
+    let share_price = vault.share_price()?;
+    
+    emit!(NavUpdateEvent {
+        old_tvl,
+        new_tvl,
+        idle_lamports,
+        pending_unstake_lamports: vault.pending_unstake_lamports,
+        allocation_values,
+        share_price,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("NAV updated");
+    msg!("Total TVL: {} lamports (was {})", new_tvl, old_tvl);
+    msg!("Share price: {}", share_price);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateNav<'info> {
+    /// The vault state account
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, VaultState>,
+    
+    /// Vault's SOL holding account
+    /// CHECK: This is a PDA that holds SOL
+    #[account(
+        seeds = [VAULT_SOL_SEED],
+        bump
+    )]
+    pub vault_sol_account: UncheckedAccount<'info>,
+}
@@ -2,24 +2,43 @@
 
 use anchor_lang::prelude::*;
 
+use anchor_spl::token::Token;
+
 use crate::constants::*;
 use crate::errors::VaultError;
 use crate::state::*;
+use crate::utils::adapter::{
+    adapter_deposit, adapter_instant_unstake, custody_value, AdapterOperation, VaultAccounts,
+};
+use crate::utils::protocols::supports_instant_unstake;
 
 /// Rebalance the vault's LST allocations
 /// 
 /// This instruction adjusts the actual allocations to match target allocations.
 /// Can be called by anyone, but will only execute if deviation exceeds threshold.
 /// 
+/// Every allocation whose deviation meets the threshold is moved through its
+/// protocol adapter: overweight protocols are liquid-unstaked first, then
+/// underweight protocols are staked from idle SOL. `remaining_accounts`
+/// holds one group per moved allocation, overweight ones first and each in
+/// allocation order:
+/// `[protocol_config, vault_token_account, ...adapter accounts]`.
+/// 
+/// Instant unstake fees are taken out of `total_tvl`. Targets are sized
+/// from the TVL before the rebalance.
+/// 
 /// # Arguments
 /// * `ctx` - The context containing all accounts
-pub fn rebalance_handler(ctx: Context<Rebalance>) -> Result<()> {
+pub fn rebalance_handler<'info>(ctx: Context<'_, '_, 'info, 'info, Rebalance<'info>>) -> Result<()> {
     let vault = &mut ctx.accounts.vault;
     let clock = Clock::get()?;
     
     // Check vault is not paused
     require!(!vault.config.is_paused, VaultError::VaultPaused);
     
+    // Targets are sized from total_tvl, so it must reflect current holdings
+    vault.require_fresh_nav(clock.slot)?;
+    
     // Check minimum time between rebalances
     let time_since_last = clock.unix_timestamp
         .checked_sub(vault.last_rebalance)
@@ -40,11 +59,7 @@ pub fn rebalance_handler(ctx: Context<Rebalance>) -> Result<()> {
         old_allocations[i] = alloc.current_bps;
         
         // Calculate deviation from target
-        let deviation = if alloc.current_bps > alloc.target_bps {
-            alloc.current_bps - alloc.target_bps
-        } else {
-            alloc.target_bps - alloc.current_bps
-        };
+        let deviation = alloc.current_bps.abs_diff(alloc.target_bps);
         
         if deviation > max_deviation {
             max_deviation = deviation;
@@ -58,30 +73,108 @@ pub fn rebalance_handler(ctx: Context<Rebalance>) -> Result<()> {
     // Check if rebalance is necessary
     require!(needs_rebalance, VaultError::RebalanceThresholdNotMet);
     
-    // Perform rebalancing logic
-    // In a real implementation, this would:
-    // 1. Calculate required swaps between protocols
-    // 2. Unstake from overweight protocols
-    // 3. Stake to underweight protocols
-    // 4. Use Jupiter for efficient routing
-    
-    // For now, we update current allocations to target (placeholder)
-    let mut new_allocations = [0u16; MAX_PROTOCOLS];
+    // Divest from overweight protocols, then invest idle SOL into
+    // underweight ones
     let total_tvl = vault.total_tvl; // Use local copy to avoid borrow conflict
+    let threshold = vault.config.rebalance_threshold_bps;
+    let max_slippage_bps = vault.config.max_slippage_bps;
+    let vault_info = vault.to_account_info();
+    let vault_sol_info = ctx.accounts.vault_sol_account.to_account_info();
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+    let token_program_info = ctx.accounts.token_program.to_account_info();
+    let vault_bump = vault.bump;
+    let vault_sol_bump = ctx.bumps.vault_sol_account;
+    let idle_reserve = Rent::get()?.minimum_balance(0);
     
-    for i in 0..vault.num_allocations as usize {
-        let alloc = &mut vault.allocations[i];
-        
-        // Calculate new amount based on target allocation
-        let new_amount = (total_tvl as u128)
-            .checked_mul(alloc.target_bps as u128)
-            .ok_or(VaultError::MathOverflow)?
-            .checked_div(TOTAL_ALLOCATION_BPS as u128)
-            .ok_or(VaultError::DivisionByZero)? as u64;
-        
-        alloc.amount = new_amount;
-        alloc.current_bps = alloc.target_bps;
-        new_allocations[i] = alloc.current_bps;
+    let mut remaining = ctx.remaining_accounts;
+    for overweight in [true, false] {
+        for i in 0..vault.num_allocations as usize {
+            let alloc = &vault.allocations[i];
+            if (alloc.current_bps > alloc.target_bps) != overweight
+                || alloc.current_bps.abs_diff(alloc.target_bps) < threshold
+            {
+                continue;
+            }
+            
+            let target_amount = (total_tvl as u128)
+                .checked_mul(alloc.target_bps as u128)
+                .ok_or(VaultError::MathOverflow)?
+                .checked_div(TOTAL_ALLOCATION_BPS as u128)
+                .ok_or(VaultError::DivisionByZero)? as u64;
+            
+            // Load and validate this allocation's account group
+            require!(remaining.len() >= 2, VaultError::MissingProtocolAccounts);
+            let config = Account::<ProtocolConfig>::try_from(&remaining[0])?;
+            require!(config.protocol == alloc.protocol, VaultError::InvalidProtocolAccount);
+            require_keys_eq!(
+                remaining[1].key(),
+                config.vault_token_account,
+                VaultError::InvalidProtocolAccount
+            );
+            
+            let adapter = config.load_adapter();
+            let operation = if overweight {
+                AdapterOperation::LiquidUnstake
+            } else {
+                AdapterOperation::Deposit
+            };
+            let group_len = 2 + adapter.required_accounts(operation);
+            require!(remaining.len() >= group_len, VaultError::MissingProtocolAccounts);
+            let protocol_accounts = &remaining[2..group_len];
+            let vault_token_info = &remaining[1];
+            let vault_accounts = VaultAccounts {
+                vault: &vault_info,
+                vault_sol: &vault_sol_info,
+                vault_token_account: vault_token_info,
+                system_program: &system_program_info,
+                token_program: &token_program_info,
+                vault_bump,
+                vault_sol_bump,
+            };
+            remaining = &remaining[group_len..];
+            
+            if overweight {
+                let excess = alloc.amount.saturating_sub(target_amount);
+                if !supports_instant_unstake(&config) {
+                    msg!("Protocol {} has no instant unstake, skipping", config.protocol);
+                } else if excess > 0 {
+                    let (_, fee) = adapter_instant_unstake(
+                        adapter.as_ref(),
+                        &config,
+                        &vault_accounts,
+                        protocol_accounts,
+                        excess,
+                        max_slippage_bps,
+                    )?;
+                    
+                    // The unstake fee leaves the vault
+                    vault.total_tvl = vault.total_tvl
+                        .checked_sub(fee)
+                        .ok_or(VaultError::MathUnderflow)?;
+                }
+            } else {
+                let deficit = target_amount.saturating_sub(alloc.amount);
+                let idle = vault.spendable_idle_lamports(vault_sol_info.lamports(), idle_reserve);
+                let stake_amount = deficit.min(idle);
+                if !config.is_active {
+                    msg!("Protocol {} is disabled, skipping", config.protocol);
+                } else if alloc.is_quarantined {
+                    msg!("Protocol {} is quarantined, skipping", config.protocol);
+                } else if stake_amount > 0 {
+                    adapter_deposit(adapter.as_ref(), &vault_accounts, protocol_accounts, stake_amount)?;
+                }
+            }
+            
+            // Re-value the allocation from its custody balance
+            vault.allocations[i].amount =
+                custody_value(adapter.as_ref(), vault_token_info, protocol_accounts)?;
+        }
+    }
+    
+    vault.update_current_bps()?;
+    let mut new_allocations = [0u16; MAX_PROTOCOLS];
+    for (new_bps, alloc) in new_allocations.iter_mut().zip(vault.allocations.iter()) {
+        *new_bps = alloc.current_bps;
     }
     
     // Update vault state
@@ -114,6 +207,21 @@ pub struct Rebalance<'info> {
     )]
     pub vault: Account<'info, VaultState>,
     
+    /// Vault's SOL holding account
+    /// CHECK: This is a PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [VAULT_SOL_SEED],
+        bump
+    )]
+    pub vault_sol_account: UncheckedAccount<'info>,
+    
     /// Anyone can trigger rebalance if threshold is met
     pub rebalancer: Signer<'info>,
+    
+    /// System program
+    pub system_program: Program<'info, System>,
+    
+    /// Token program
+    pub token_program: Program<'info, Token>,
 }
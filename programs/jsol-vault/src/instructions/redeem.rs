@@ -0,0 +1,330 @@
+//! In-kind redemption instruction
+//!
+//! Lets holders exit immediately by taking the vault's underlying assets
+//! instead of waiting out the unstake queue.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, accessor, Burn, Mint, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::VaultError;
+use crate::state::*;
+use crate::utils::math::calculate_value_for_shares;
+
+/// Redeem jSOLi for a pro-rata slice of every vault asset
+///
+/// Burns `shares` and pays the redeemer `shares / total_shares` of each
+/// protocol's custody balance and of the idle SOL above the vault SOL
+/// account's rent-exempt minimum, less the idle SOL queued for open
+/// withdrawal requests. There is no unstake delay. Amounts round
+/// down, so dust stays with the remaining holders.
+///
+/// `remaining_accounts` holds one group per allocation, in allocation order:
+/// `[protocol_config, vault_token_account, user_token_account]`, where the
+/// user token account must hold the protocol's LST mint.
+///
+/// # Arguments
+/// * `ctx` - The context containing all accounts
+/// * `shares` - Number of jSOLi shares to redeem
+pub fn redeem_in_kind_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RedeemInKind<'info>>,
+    shares: u64,
+) -> Result<()> {
+    require!(shares > 0, VaultError::ZeroAmount);
+    
+    let clock = Clock::get()?;
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault = &mut ctx.accounts.vault;
+    
+    // Check vault is not paused
+    require!(!vault.config.is_paused, VaultError::VaultPaused);
+    
+    // Allocation values are sliced alongside balances, so they must be current
+    vault.require_fresh_nav(clock.slot)?;
+    
+    // Check user has enough shares
+    require!(
+        ctx.accounts.user_account.shares >= shares,
+        VaultError::InsufficientShares
+    );
+    
+    let share_price = vault.share_price()?;
+    let total_shares = vault.total_shares;
+    let num_allocations = vault.num_allocations as usize;
+    require!(
+        ctx.remaining_accounts.len() >= num_allocations * 3,
+        VaultError::MissingProtocolAccounts
+    );
+    
+    let vault_seeds = &[VAULT_SEED, &[vault.bump]];
+    let signer_seeds = &[&vault_seeds[..]];
+    
+    // Pay out each protocol's slice of its custody balance
+    let mut assets = Vec::with_capacity(num_allocations);
+    let mut total_value: u64 = 0;
+    
+    for (i, group) in ctx.remaining_accounts.chunks_exact(3).take(num_allocations).enumerate() {
+        let config = Account::<ProtocolConfig>::try_from(&group[0])?;
+        require!(
+            config.protocol == vault.allocations[i].protocol,
+            VaultError::InvalidProtocolAccount
+        );
+        require_keys_eq!(
+            group[1].key(),
+            config.vault_token_account,
+            VaultError::InvalidProtocolAccount
+        );
+        let user_token_account = Account::<TokenAccount>::try_from(&group[2])?;
+        require_keys_eq!(user_token_account.mint, config.mint, VaultError::InvalidMint);
+    
+        let custody_balance = accessor::amount(&group[1])?;
+        let (amount, value) = redeem_allocation(&mut vault.allocations[i], custody_balance, shares, total_shares)?;
+        if amount == 0 {
+            continue;
+        }
+    
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: group[1].clone(),
+                to: group[2].clone(),
+                authority: vault_info.clone(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, amount)?;
+    
+        total_value = total_value
+            .checked_add(value)
+            .ok_or(VaultError::MathOverflow)?;
+    
+        assets.push(RedeemedAsset {
+            protocol: config.protocol,
+            mint: config.mint,
+            amount,
+            value,
+        });
+    }
+    
+    // Pay out the slice of idle SOL, keeping the holding account rent-exempt
+    // and leaving the SOL queued for withdrawal requests in place
+    let vault_sol_info = ctx.accounts.vault_sol_account.to_account_info();
+    let idle_lamports = vault.spendable_idle_lamports(
+        vault_sol_info.lamports(),
+        Rent::get()?.minimum_balance(0),
+    );
+    let sol_lamports = calculate_value_for_shares(shares, idle_lamports, total_shares)?;
+    if sol_lamports > 0 {
+        let vault_sol_seeds = &[VAULT_SOL_SEED, &[ctx.bumps.vault_sol_account]];
+        let vault_sol_signer = &[&vault_sol_seeds[..]];
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: vault_sol_info,
+                to: ctx.accounts.user.to_account_info(),
+            },
+            vault_sol_signer,
+        );
+        anchor_lang::system_program::transfer(transfer_ctx, sol_lamports)?;
+        total_value = total_value
+            .checked_add(sol_lamports)
+            .ok_or(VaultError::MathOverflow)?;
+    }
+    
+    // Burn jSOLi tokens - user is authority of their own token account
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.jsoli_mint.to_account_info(),
+            from: ctx.accounts.user_jsol_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::burn(burn_ctx, shares)?;
+    
+    // Update vault state
+    settle_redemption(vault, shares, total_value)?;
+    
+    // Update user account
+    let user_account = &mut ctx.accounts.user_account;
+    user_account.shares = user_account.shares
+        .checked_sub(shares)
+        .ok_or(VaultError::MathUnderflow)?;
+    user_account.total_withdrawn = user_account.total_withdrawn
+        .checked_add(total_value)
+        .ok_or(VaultError::MathOverflow)?;
+    user_account.last_activity = clock.unix_timestamp;
+    
+    let num_assets = assets.len();
+    emit!(RedeemInKindEvent {
+        user: ctx.accounts.user.key(),
+        shares,
+        share_price,
+        sol_lamports,
+        assets,
+        total_value,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("In-kind redemption completed");
+    msg!("Shares burned: {}", shares);
+    msg!("Paid {} lamports of SOL and {} LSTs", sol_lamports, num_assets);
+    msg!("Total value: {} lamports", total_value);
+    
+    Ok(())
+}
+
+/// Take a redemption's slice of one allocation
+/// 
+/// Returns the LST amount paid out of `custody_balance` and the value
+/// taken off the allocation's recorded amount, each `shares / total_shares`
+/// rounded down. An allocation whose LST slice rounds to zero is left
+/// untouched.
+pub fn redeem_allocation(
+    alloc: &mut Allocation,
+    custody_balance: u64,
+    shares: u64,
+    total_shares: u64,
+) -> Result<(u64, u64)> {
+    let amount = calculate_value_for_shares(shares, custody_balance, total_shares)?;
+    if amount == 0 {
+        return Ok((0, 0));
+    }
+    
+    let value = calculate_value_for_shares(shares, alloc.amount, total_shares)?;
+    alloc.amount = alloc.amount
+        .checked_sub(value)
+        .ok_or(VaultError::MathUnderflow)?;
+    Ok((amount, value))
+}
+
+/// Remove redeemed shares and the value paid for them from the vault
+pub fn settle_redemption(vault: &mut VaultState, shares: u64, total_value: u64) -> Result<()> {
+    vault.total_tvl = vault.total_tvl
+        .checked_sub(total_value)
+        .ok_or(VaultError::MathUnderflow)?;
+    vault.total_shares = vault.total_shares
+        .checked_sub(shares)
+        .ok_or(VaultError::MathUnderflow)?;
+    vault.update_current_bps()
+}
+
+#[derive(Accounts)]
+pub struct RedeemInKind<'info> {
+    /// The vault state account
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Box<Account<'info, VaultState>>,
+    
+    /// The jSOLi token mint
+    #[account(
+        mut,
+        seeds = [JSOLI_MINT_SEED],
+        bump
+    )]
+    pub jsoli_mint: Box<Account<'info, Mint>>,
+    
+    /// User's jSOLi token account
+    #[account(
+        mut,
+        associated_token::mint = jsoli_mint,
+        associated_token::authority = user
+    )]
+    pub user_jsol_account: Box<Account<'info, TokenAccount>>,
+    
+    /// User account tracking position
+    #[account(
+        mut,
+        seeds = [USER_ACCOUNT_SEED, user.key().as_ref()],
+        bump = user_account.bump,
+        constraint = user_account.owner == user.key() @ VaultError::Unauthorized
+    )]
+    pub user_account: Box<Account<'info, UserAccount>>,
+    
+    /// Vault's SOL holding account
+    /// CHECK: This is a PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [VAULT_SOL_SEED],
+        bump
+    )]
+    pub vault_sol_account: UncheckedAccount<'info>,
+    
+    /// The user redeeming shares
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Token program
+    pub token_program: Program<'info, Token>,
+    
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const SOL: u64 = 1_000_000_000;
+    
+    #[test]
+    fn test_partial_redemption() {
+        // 60 SOL in protocol 0 as 50 LST, 30 SOL in protocol 1 as 28 LST,
+        // 10 SOL idle, against 100 shares
+        let mut vault = test_vault(&[(0, 60 * SOL), (1, 30 * SOL)]);
+        vault.total_tvl = 100 * SOL;
+        vault.total_shares = 100 * SOL;
+        let shares = 25 * SOL;
+        let total_shares = vault.total_shares;
+        
+        let (amount, value) = redeem_allocation(&mut vault.allocations[0], 50 * SOL, shares, total_shares).unwrap();
+        assert_eq!((amount, value), (12_500_000_000, 15 * SOL));
+        assert_eq!(vault.allocations[0].amount, 45 * SOL);
+        
+        let (amount, value) = redeem_allocation(&mut vault.allocations[1], 28 * SOL, shares, total_shares).unwrap();
+        assert_eq!((amount, value), (7 * SOL, 7_500_000_000));
+        assert_eq!(vault.allocations[1].amount, 22_500_000_000);
+        
+        let sol_lamports = calculate_value_for_shares(shares, 10 * SOL, total_shares).unwrap();
+        assert_eq!(sol_lamports, 2_500_000_000);
+        
+        // A quarter of the shares takes a quarter of the TVL
+        settle_redemption(&mut vault, shares, 15 * SOL + 7_500_000_000 + sol_lamports).unwrap();
+        assert_eq!(vault.total_tvl, 75 * SOL);
+        assert_eq!(vault.total_shares, 75 * SOL);
+        assert_eq!(vault.allocations[0].current_bps, 6_000);
+        assert_eq!(vault.allocations[1].current_bps, 3_000);
+    }
+    
+    #[test]
+    fn test_redemption_dust_stays_in_vault() {
+        let mut vault = test_vault(&[(0, 1_000)]);
+        vault.total_tvl = 1_003;
+        vault.total_shares = 3;
+        
+        // One of three shares: 333 of 1000 LST and of 1000 lamports of value
+        let (amount, value) = redeem_allocation(&mut vault.allocations[0], 1_000, 1, 3).unwrap();
+        assert_eq!((amount, value), (333, 333));
+        assert_eq!(vault.allocations[0].amount, 667);
+        
+        // An allocation whose slice rounds to nothing is left alone
+        let mut small = Allocation { amount: 2, ..Allocation::default() };
+        assert_eq!(redeem_allocation(&mut small, 2, 1, 3).unwrap(), (0, 0));
+        assert_eq!(small.amount, 2);
+        
+        // 1 of 3 lamports idle rounds down too
+        let sol_lamports = calculate_value_for_shares(1, 3, 3).unwrap();
+        assert_eq!(sol_lamports, 1);
+        
+        let price_before = vault.share_price().unwrap();
+        settle_redemption(&mut vault, 1, value + sol_lamports).unwrap();
+        assert_eq!(vault.total_tvl, 669);
+        assert_eq!(vault.total_shares, 2);
+        
+        // The remaining holders keep the rounding dust
+        assert!(vault.share_price().unwrap() > price_before);
+    }
+}
@@ -3,26 +3,34 @@
 //! This module handles staking and unstaking to/from various LST protocols.
 
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::token::{Token, TokenAccount};
 
 use crate::constants::*;
 use crate::errors::VaultError;
 use crate::state::*;
+use crate::utils::adapter::{
+    adapter_deposit, adapter_instant_unstake, adapter_unstake, custody_value, VaultAccounts,
+};
+use crate::utils::protocols::supports_instant_unstake;
 
 /// Stake SOL to a specific LST protocol
 /// 
 /// This instruction takes SOL from the vault and stakes it to the specified
-/// protocol, receiving the corresponding LST token.
+/// protocol, receiving the corresponding LST token. Protocol-specific
+/// accounts are passed in `remaining_accounts`, in the order documented by
+/// the protocol's `LstAdapter` implementation.
 /// 
 /// # Arguments
 /// * `ctx` - The context containing all accounts
 /// * `protocol` - The LST protocol to stake to
 /// * `amount` - Amount of lamports to stake
-pub fn stake_handler(
-    ctx: Context<StakeToProtocol>,
+pub fn stake_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, StakeToProtocol<'info>>,
     protocol: u8,
     amount: u64,
 ) -> Result<()> {
-    let vault = &mut ctx.accounts.vault;
+    let vault = &ctx.accounts.vault;
     
     // Check vault is not paused
     require!(!vault.config.is_paused, VaultError::VaultPaused);
@@ -43,29 +51,54 @@ pub fn stake_handler(
     }
     
     require!(protocol_found, VaultError::UnsupportedProtocol);
+    require!(ctx.accounts.protocol_config.is_active, VaultError::ProtocolDisabled);
+    require!(
+        !vault.allocations[protocol_index].is_quarantined,
+        VaultError::ProtocolQuarantined
+    );
     
-    // In a real implementation, this would:
-    // 1. Call the appropriate protocol's stake function
-    // 2. Handle the LST token receipt
-    // 3. Update internal accounting
+    // Idle SOL queued for withdrawal requests stays in the vault
+    let spendable = vault.spendable_idle_lamports(
+        ctx.accounts.vault_sol_account.lamports(),
+        Rent::get()?.minimum_balance(0),
+    );
+    require!(amount <= spendable, VaultError::InsufficientLiquidity);
     
-    // For now, we just update the allocation amount
-    vault.allocations[protocol_index].amount = vault.allocations[protocol_index].amount
-        .checked_add(amount)
-        .ok_or(VaultError::MathOverflow)?;
+    // Stake through the protocol, then value the allocation from the
+    // custody balance at the live exchange rate
+    let adapter = ctx.accounts.protocol_config.load_adapter();
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault_sol_info = ctx.accounts.vault_sol_account.to_account_info();
+    let vault_token_info = ctx.accounts.vault_lst_account.to_account_info();
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+    let token_program_info = ctx.accounts.token_program.to_account_info();
+    let vault_accounts = VaultAccounts {
+        vault: &vault_info,
+        vault_sol: &vault_sol_info,
+        vault_token_account: &vault_token_info,
+        system_program: &system_program_info,
+        token_program: &token_program_info,
+        vault_bump: ctx.accounts.vault.bump,
+        vault_sol_bump: ctx.bumps.vault_sol_account,
+    };
+    let staked_value = adapter_deposit(
+        adapter.as_ref(),
+        &vault_accounts,
+        ctx.remaining_accounts,
+        amount,
+    )?;
+    
+    let custody = custody_value(adapter.as_ref(), &vault_token_info, ctx.remaining_accounts)?;
+    
+    let vault = &mut ctx.accounts.vault;
+    vault.allocations[protocol_index].amount = custody;
     
     // Recalculate current allocation percentages
-    if vault.total_tvl > 0 {
-        for i in 0..vault.num_allocations as usize {
-            vault.allocations[i].current_bps = ((vault.allocations[i].amount as u128)
-                .checked_mul(TOTAL_ALLOCATION_BPS as u128)
-                .ok_or(VaultError::MathOverflow)?
-                .checked_div(vault.total_tvl as u128)
-                .ok_or(VaultError::DivisionByZero)?) as u16;
-        }
-    }
+    vault.update_current_bps()?;
     
     msg!("Staked {} lamports to protocol {}", amount, protocol);
+    msg!("Received LST worth {} lamports", staked_value);
+    msg!("Protocol {} custody value: {} lamports", protocol, custody);
     
     Ok(())
 }
@@ -73,17 +106,28 @@ pub fn stake_handler(
 /// Unstake from a specific LST protocol
 /// 
 /// This instruction initiates unstaking from the specified protocol.
+/// Protocols with delayed unstaking open a ticket that pays out on claim;
+/// others redeem immediately into the vault through the same checks as
+/// `instant_unstake`, so they must support instant unstaking and the fee is
+/// taken out of `total_tvl`. Protocol-specific accounts are passed in
+/// `remaining_accounts`.
+/// 
+/// Tickets are allocated at the next `[UNSTAKE_TICKET_SEED, protocol,
+/// index]` PDA, paid for by the authority even if someone already funded
+/// the address, and recorded in the protocol's
+/// `PendingUnstakes` list until `claim_unstake` pays them out. Their value
+/// counts towards NAV through `pending_unstake_lamports` in the meantime.
 /// 
 /// # Arguments
 /// * `ctx` - The context containing all accounts
 /// * `protocol` - The LST protocol to unstake from
 /// * `amount` - Amount of LST value to unstake (in lamports equivalent)
-pub fn unstake_handler(
-    ctx: Context<UnstakeFromProtocol>,
+pub fn unstake_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UnstakeFromProtocol<'info>>,
     protocol: u8,
     amount: u64,
 ) -> Result<()> {
-    let vault = &mut ctx.accounts.vault;
+    let vault = &ctx.accounts.vault;
     
     // Validate amount
     require!(amount > 0, VaultError::ZeroAmount);
@@ -108,33 +152,391 @@ pub fn unstake_handler(
         VaultError::InsufficientLiquidity
     );
     
-    // In a real implementation, this would:
-    // 1. Call the appropriate protocol's unstake function
-    // 2. Create ticket/request for delayed unstaking
-    // 3. Track pending unstake requests
+    let adapter = ctx.accounts.protocol_config.load_adapter();
+    let pending = &mut ctx.accounts.pending_unstakes;
+    pending.bump = ctx.bumps.pending_unstakes;
+    pending.protocol = protocol;
     
-    // For now, update the allocation amount
-    vault.allocations[protocol_index].amount = vault.allocations[protocol_index].amount
-        .checked_sub(amount)
-        .ok_or(VaultError::MathUnderflow)?;
+    // Allocate the ticket for a delayed unstake at the next ticket PDA
+    let ticket_info = match adapter.ticket_layout() {
+        Some((space, owner)) => {
+            let ticket = ctx.accounts.ticket_account
+                .as_ref()
+                .ok_or(VaultError::MissingProtocolAccounts)?
+                .to_account_info();
+            let index_bytes = pending.next_ticket_index.to_le_bytes();
+            let (expected, bump) = Pubkey::find_program_address(
+                &[UNSTAKE_TICKET_SEED, &[protocol], &index_bytes],
+                ctx.program_id,
+            );
+            require_keys_eq!(ticket.key(), expected, VaultError::InvalidProtocolAccount);
+            
+            let ticket_seeds = &[UNSTAKE_TICKET_SEED, &[protocol], &index_bytes, &[bump]];
+            init_ticket_account(
+                &ctx.accounts.authority.to_account_info(),
+                &ticket,
+                &ctx.accounts.system_program.to_account_info(),
+                space,
+                &owner,
+                &[&ticket_seeds[..]],
+            )?;
+            Some(ticket)
+        }
+        None => {
+            // Without a ticket the pool redeems immediately, so it must
+            // be a registered instant unstake with a bounded fee
+            require!(
+                supports_instant_unstake(&ctx.accounts.protocol_config),
+                VaultError::UnsupportedProtocol
+            );
+            None
+        }
+    };
     
-    // Recalculate current allocation percentages
-    if vault.total_tvl > 0 {
-        for i in 0..vault.num_allocations as usize {
-            vault.allocations[i].current_bps = ((vault.allocations[i].amount as u128)
-                .checked_mul(TOTAL_ALLOCATION_BPS as u128)
-                .ok_or(VaultError::MathOverflow)?
-                .checked_div(vault.total_tvl as u128)
-                .ok_or(VaultError::DivisionByZero)?) as u16;
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault_sol_info = ctx.accounts.vault_sol_account.to_account_info();
+    let vault_token_info = ctx.accounts.vault_lst_account.to_account_info();
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+    let token_program_info = ctx.accounts.token_program.to_account_info();
+    let vault_accounts = VaultAccounts {
+        vault: &vault_info,
+        vault_sol: &vault_sol_info,
+        vault_token_account: &vault_token_info,
+        system_program: &system_program_info,
+        token_program: &token_program_info,
+        vault_bump: ctx.accounts.vault.bump,
+        vault_sol_bump: ctx.bumps.vault_sol_account,
+    };
+    let lst_before = ctx.accounts.vault_lst_account.amount;
+    let fee = match ticket_info.as_ref() {
+        Some(ticket) => {
+            adapter_unstake(
+                adapter.as_ref(),
+                &vault_accounts,
+                ctx.remaining_accounts,
+                amount,
+                Some(ticket),
+            )?;
+            0
         }
+        None => {
+            let (received, fee) = adapter_instant_unstake(
+                adapter.as_ref(),
+                &ctx.accounts.protocol_config,
+                &vault_accounts,
+                ctx.remaining_accounts,
+                amount,
+                ctx.accounts.vault.config.max_slippage_bps,
+            )?;
+            msg!("Received {} lamports, fee {}", received, fee);
+            fee
+        }
+    };
+    
+    let custody = custody_value(adapter.as_ref(), &vault_token_info, ctx.remaining_accounts)?;
+    
+    let vault = &mut ctx.accounts.vault;
+    vault.allocations[protocol_index].amount = custody;
+    
+    // An immediate redemption's fee leaves the vault
+    vault.total_tvl = vault.total_tvl
+        .checked_sub(fee)
+        .ok_or(VaultError::MathUnderflow)?;
+    
+    // Record the ticket until it is claimed
+    if let Some(ticket) = ticket_info {
+        let clock = Clock::get()?;
+        let lamports = adapter.ticket_lamports(&vault_accounts, &ticket)?;
+        ctx.accounts.vault_lst_account.reload()?;
+        let lst_amount = lst_before
+            .checked_sub(ctx.accounts.vault_lst_account.amount)
+            .ok_or(VaultError::MathUnderflow)?;
+        
+        record_unstake_ticket(
+            vault,
+            &mut ctx.accounts.pending_unstakes,
+            PendingTicket {
+                ticket: ticket.key(),
+                lst_amount,
+                lamports,
+                created_epoch: clock.epoch,
+            },
+        )?;
+        
+        emit!(UnstakeOrderedEvent {
+            protocol,
+            ticket: ticket.key(),
+            lst_amount,
+            lamports,
+            epoch: clock.epoch,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Opened unstake ticket {} for {} lamports", ticket.key(), lamports);
     }
     
+    // Recalculate current allocation percentages
+    vault.update_current_bps()?;
+    
     msg!("Initiated unstake of {} lamports from protocol {}", amount, protocol);
+    msg!("Protocol {} custody value: {} lamports", protocol, custody);
+    
+    Ok(())
+}
+
+/// Lamports to add to an account holding `lamports` to reach `required`
+fn rent_top_up(lamports: u64, required: u64) -> u64 {
+    required.saturating_sub(lamports)
+}
+
+/// Allocate an unstake ticket at its PDA, owned by `owner`
+/// 
+/// Ticket addresses are predictable, so anyone can send lamports to the
+/// next one ahead of the unstake. As with Anchor's `init`, an address that
+/// already holds lamports is topped up to the rent-exempt minimum by
+/// `payer`, then allocated and assigned through the ticket's signer seeds
+/// rather than created.
+fn init_ticket_account<'info>(
+    payer: &AccountInfo<'info>,
+    ticket: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    owner: &Pubkey,
+    ticket_signer: &[&[&[u8]]],
+) -> Result<()> {
+    let required = Rent::get()?.minimum_balance(space);
+    let lamports = ticket.lamports();
+    if lamports == 0 {
+        let create_ctx = CpiContext::new_with_signer(
+            system_program.clone(),
+            CreateAccount {
+                from: payer.clone(),
+                to: ticket.clone(),
+            },
+            ticket_signer,
+        );
+        return system_program::create_account(create_ctx, required, space as u64, owner);
+    }
+    
+    let top_up = rent_top_up(lamports, required);
+    if top_up > 0 {
+        let transfer_ctx = CpiContext::new(
+            system_program.clone(),
+            Transfer {
+                from: payer.clone(),
+                to: ticket.clone(),
+            },
+        );
+        system_program::transfer(transfer_ctx, top_up)?;
+    }
+    
+    let allocate_ctx = CpiContext::new_with_signer(
+        system_program.clone(),
+        Allocate {
+            account_to_allocate: ticket.clone(),
+        },
+        ticket_signer,
+    );
+    system_program::allocate(allocate_ctx, space as u64)?;
+    
+    let assign_ctx = CpiContext::new_with_signer(
+        system_program.clone(),
+        Assign {
+            account_to_assign: ticket.clone(),
+        },
+        ticket_signer,
+    );
+    system_program::assign(assign_ctx, owner)
+}
+
+/// Record an opened unstake ticket
+/// 
+/// Adds the ticket to the protocol's pending list, advances the ticket
+/// index and counts its value in `pending_unstake_lamports` until claimed.
+fn record_unstake_ticket(
+    vault: &mut VaultState,
+    pending: &mut PendingUnstakes,
+    ticket: PendingTicket,
+) -> Result<()> {
+    pending.push(ticket)?;
+    pending.next_ticket_index = pending.next_ticket_index
+        .checked_add(1)
+        .ok_or(VaultError::MathOverflow)?;
+    vault.pending_unstake_lamports = vault.pending_unstake_lamports
+        .checked_add(ticket.lamports)
+        .ok_or(VaultError::MathOverflow)?;
+    Ok(())
+}
+
+/// Instantly unstake from a specific LST protocol
+/// 
+/// Liquid-unstakes the LST equivalent of `amount` straight into the vault
+/// SOL PDA, so withdrawals can be met when the idle buffer is short. Only
+/// protocols registered with instant unstaking are accepted. The fee the
+/// pool charges may exceed the registry's `instant_unstake_fee_bps` by at
+/// most `max_slippage_bps`, otherwise the instruction fails with
+/// `SlippageExceeded`. The fee is taken out of `total_tvl`.
+/// Protocol-specific accounts are passed in `remaining_accounts`.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all accounts
+/// * `protocol` - The LST protocol to unstake from
+/// * `amount` - Amount of LST value to unstake (in lamports equivalent)
+pub fn instant_unstake_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, InstantUnstake<'info>>,
+    protocol: u8,
+    amount: u64,
+) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    
+    // Validate amount
+    require!(amount > 0, VaultError::ZeroAmount);
+    
+    // Validate protocol is configured and can unstake instantly
+    let protocol_index = vault.allocations[..vault.num_allocations as usize]
+        .iter()
+        .position(|alloc| alloc.protocol == protocol)
+        .ok_or(VaultError::UnsupportedProtocol)?;
+    require!(
+        supports_instant_unstake(&ctx.accounts.protocol_config),
+        VaultError::UnsupportedProtocol
+    );
+    
+    // Check sufficient balance
+    require!(
+        vault.allocations[protocol_index].amount >= amount,
+        VaultError::InsufficientLiquidity
+    );
+    
+    let clock = Clock::get()?;
+    let adapter = ctx.accounts.protocol_config.load_adapter();
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault_sol_info = ctx.accounts.vault_sol_account.to_account_info();
+    let vault_token_info = ctx.accounts.vault_lst_account.to_account_info();
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+    let token_program_info = ctx.accounts.token_program.to_account_info();
+    let vault_accounts = VaultAccounts {
+        vault: &vault_info,
+        vault_sol: &vault_sol_info,
+        vault_token_account: &vault_token_info,
+        system_program: &system_program_info,
+        token_program: &token_program_info,
+        vault_bump: ctx.accounts.vault.bump,
+        vault_sol_bump: ctx.bumps.vault_sol_account,
+    };
+    let (received, fee) = adapter_instant_unstake(
+        adapter.as_ref(),
+        &ctx.accounts.protocol_config,
+        &vault_accounts,
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.vault.config.max_slippage_bps,
+    )?;
+    
+    let custody = custody_value(adapter.as_ref(), &vault_token_info, ctx.remaining_accounts)?;
+    
+    // Update vault state
+    let vault = &mut ctx.accounts.vault;
+    vault.allocations[protocol_index].amount = custody;
+    vault.total_tvl = vault.total_tvl
+        .checked_sub(fee)
+        .ok_or(VaultError::MathUnderflow)?;
+    vault.update_current_bps()?;
+    
+    emit!(InstantUnstakeEvent {
+        protocol,
+        lamports: amount,
+        received,
+        fee,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Instantly unstaked {} lamports from protocol {}", amount, protocol);
+    msg!("Received {} lamports, fee {}", received, fee);
     
     Ok(())
 }
 
+/// Claim a matured delayed-unstake ticket
+/// 
+/// Pays the ticket out into the vault SOL PDA and removes it from the
+/// protocol's pending list. The ticket's value moves from
+/// `pending_unstake_lamports` to idle SOL; anything received on top, such
+/// as the ticket's rent, accrues to holders. Can be called by anyone.
+/// Protocol-specific accounts are passed in `remaining_accounts`.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all accounts
+/// * `protocol` - The LST protocol that issued the ticket
+pub fn claim_unstake_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimUnstake<'info>>,
+    protocol: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let ticket_key = ctx.accounts.ticket_account.key();
+    let entry = ctx.accounts.pending_unstakes.remove(&ticket_key)?;
+    
+    let adapter = ctx.accounts.protocol_config.load_adapter();
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault_sol_info = ctx.accounts.vault_sol_account.to_account_info();
+    let vault_token_info = ctx.accounts.vault_lst_account.to_account_info();
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+    let token_program_info = ctx.accounts.token_program.to_account_info();
+    let vault_accounts = VaultAccounts {
+        vault: &vault_info,
+        vault_sol: &vault_sol_info,
+        vault_token_account: &vault_token_info,
+        system_program: &system_program_info,
+        token_program: &token_program_info,
+        vault_bump: ctx.accounts.vault.bump,
+        vault_sol_bump: ctx.bumps.vault_sol_account,
+    };
+    
+    let lamports_before = vault_sol_info.lamports();
+    adapter.claim(
+        &vault_accounts,
+        &ctx.accounts.ticket_account.to_account_info(),
+        ctx.remaining_accounts,
+    )?;
+    let received = vault_sol_info.lamports()
+        .checked_sub(lamports_before)
+        .ok_or(VaultError::MathUnderflow)?;
+    
+    // Move the ticket's value from pending to idle SOL
+    settle_unstake_claim(&mut ctx.accounts.vault, entry.lamports, received)?;
+    
+    emit!(UnstakeClaimedEvent {
+        protocol,
+        ticket: ticket_key,
+        lamports: entry.lamports,
+        received,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Claimed unstake ticket {} from protocol {}", ticket_key, protocol);
+    msg!("Received {} lamports (ticket value {})", received, entry.lamports);
+    
+    Ok(())
+}
+
+/// Move a claimed ticket's value from pending to idle SOL
+/// 
+/// `ticket_lamports` is the value recorded when the ticket was opened and
+/// `received` what the claim paid into the vault SOL PDA. The difference
+/// either way lands in `total_tvl`.
+fn settle_unstake_claim(vault: &mut VaultState, ticket_lamports: u64, received: u64) -> Result<()> {
+    vault.pending_unstake_lamports = vault.pending_unstake_lamports
+        .checked_sub(ticket_lamports)
+        .ok_or(VaultError::MathUnderflow)?;
+    vault.total_tvl = vault.total_tvl
+        .checked_add(received)
+        .ok_or(VaultError::MathOverflow)?
+        .saturating_sub(ticket_lamports);
+    Ok(())
+}
+
 #[derive(Accounts)]
+#[instruction(protocol: u8)]
 pub struct StakeToProtocol<'info> {
     /// The vault state account
     #[account(
@@ -149,7 +551,7 @@ pub struct StakeToProtocol<'info> {
     /// CHECK: This is a PDA that holds SOL
     #[account(
         mut,
-        seeds = [b"vault_sol"],
+        seeds = [VAULT_SOL_SEED],
         bump
     )]
     pub vault_sol_account: UncheckedAccount<'info>,
@@ -157,14 +559,29 @@ pub struct StakeToProtocol<'info> {
     /// The vault authority
     pub authority: Signer<'info>,
     
+    /// Registry entry for the protocol
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED, &[protocol]],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// Registered vault token account receiving the LST
+    #[account(
+        mut,
+        address = protocol_config.vault_token_account @ VaultError::InvalidProtocolAccount
+    )]
+    pub vault_lst_account: Box<Account<'info, TokenAccount>>,
+    
     /// System program
     pub system_program: Program<'info, System>,
     
-    // Additional accounts for protocol-specific staking would be added here
-    // e.g., Marinade state, Jito pool, etc.
+    /// Token program
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
+#[instruction(protocol: u8)]
 pub struct UnstakeFromProtocol<'info> {
     /// The vault state account
     #[account(
@@ -175,11 +592,234 @@ pub struct UnstakeFromProtocol<'info> {
     )]
     pub vault: Account<'info, VaultState>,
     
+    /// Vault's SOL holding account
+    /// CHECK: This is a PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [VAULT_SOL_SEED],
+        bump
+    )]
+    pub vault_sol_account: UncheckedAccount<'info>,
+    
+    /// The vault authority, pays for tickets and the pending list
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// Registry entry for the protocol
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED, &[protocol]],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// Registered vault token account holding the LST
+    #[account(
+        mut,
+        address = protocol_config.vault_token_account @ VaultError::InvalidProtocolAccount
+    )]
+    pub vault_lst_account: Box<Account<'info, TokenAccount>>,
+    
+    /// Pending delayed-unstake tickets for the protocol
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PendingUnstakes::LEN,
+        seeds = [PENDING_UNSTAKES_SEED, &[protocol]],
+        bump
+    )]
+    pub pending_unstakes: Box<Account<'info, PendingUnstakes>>,
+    
+    /// Ticket PDA to allocate, required for delayed unstakes
+    /// CHECK: Address is checked against the next ticket PDA in the handler
+    #[account(mut)]
+    pub ticket_account: Option<UncheckedAccount<'info>>,
+    
+    /// System program
+    pub system_program: Program<'info, System>,
+    
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(protocol: u8)]
+pub struct InstantUnstake<'info> {
+    /// The vault state account
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, VaultState>,
+    
+    /// Vault's SOL holding account
+    /// CHECK: This is a PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [VAULT_SOL_SEED],
+        bump
+    )]
+    pub vault_sol_account: UncheckedAccount<'info>,
+    
     /// The vault authority
     pub authority: Signer<'info>,
     
+    /// Registry entry for the protocol
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED, &[protocol]],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// Registered vault token account holding the LST
+    #[account(
+        mut,
+        address = protocol_config.vault_token_account @ VaultError::InvalidProtocolAccount
+    )]
+    pub vault_lst_account: Box<Account<'info, TokenAccount>>,
+    
+    /// System program
+    pub system_program: Program<'info, System>,
+    
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(protocol: u8)]
+pub struct ClaimUnstake<'info> {
+    /// The vault state account
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, VaultState>,
+    
+    /// Vault's SOL holding account, beneficiary of the ticket
+    /// CHECK: This is a PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [VAULT_SOL_SEED],
+        bump
+    )]
+    pub vault_sol_account: UncheckedAccount<'info>,
+    
+    /// Registry entry for the protocol
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED, &[protocol]],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// Registered vault token account of the protocol
+    #[account(
+        address = protocol_config.vault_token_account @ VaultError::InvalidProtocolAccount
+    )]
+    pub vault_lst_account: Box<Account<'info, TokenAccount>>,
+    
+    /// Pending delayed-unstake tickets for the protocol
+    #[account(
+        mut,
+        seeds = [PENDING_UNSTAKES_SEED, &[protocol]],
+        bump = pending_unstakes.bump
+    )]
+    pub pending_unstakes: Box<Account<'info, PendingUnstakes>>,
+    
+    /// Ticket account being claimed
+    /// CHECK: Must be in the pending list; validated by the protocol on claim
+    #[account(mut)]
+    pub ticket_account: UncheckedAccount<'info>,
+    
     /// System program
     pub system_program: Program<'info, System>,
     
-    // Additional accounts for protocol-specific unstaking would be added here
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_rent_top_up_prefunded_ticket() {
+        let required = Rent::default().minimum_balance(200);
+        
+        // An address funded with a single lamport is topped up to rent
+        assert_eq!(rent_top_up(1, required), required - 1);
+        
+        // Funding it past the rent-exempt minimum costs the payer nothing
+        assert_eq!(rent_top_up(required, required), 0);
+        assert_eq!(rent_top_up(required + 1_000, required), 0);
+        
+        // A fresh address is created with the full minimum instead
+        assert_eq!(rent_top_up(0, required), required);
+    }
+    
+    fn ticket(lamports: u64) -> PendingTicket {
+        PendingTicket {
+            ticket: Pubkey::new_unique(),
+            lst_amount: lamports * 9 / 10,
+            lamports,
+            created_epoch: 500,
+        }
+    }
+    
+    #[test]
+    fn test_pending_unstake_lifecycle() {
+        let data = [0u8; PendingUnstakes::LEN];
+        let mut pending = PendingUnstakes::try_deserialize_unchecked(&mut &data[..]).unwrap();
+        let mut vault = test_vault(&[]);
+        vault.total_tvl = 100_000;
+        
+        let first = ticket(10_000);
+        let second = ticket(20_000);
+        record_unstake_ticket(&mut vault, &mut pending, first).unwrap();
+        record_unstake_ticket(&mut vault, &mut pending, second).unwrap();
+        assert_eq!(pending.num_tickets, 2);
+        assert_eq!(pending.next_ticket_index, 2);
+        assert_eq!(vault.pending_unstake_lamports, 30_000);
+        
+        // The first ticket pays out its rent on top of its value
+        let entry = pending.remove(&first.ticket).unwrap();
+        assert_eq!(entry, first);
+        assert_eq!(pending.num_tickets, 1);
+        assert_eq!(pending.tickets[0], second);
+        settle_unstake_claim(&mut vault, entry.lamports, 12_000).unwrap();
+        assert_eq!(vault.pending_unstake_lamports, 20_000);
+        assert_eq!(vault.total_tvl, 102_000);
+        
+        // A claimed ticket can't be claimed again
+        assert_eq!(
+            pending.remove(&first.ticket).unwrap_err(),
+            VaultError::UnstakeTicketNotFound.into()
+        );
+        
+        // The second pays out less than recorded, and holders absorb it
+        let entry = pending.remove(&second.ticket).unwrap();
+        settle_unstake_claim(&mut vault, entry.lamports, 19_500).unwrap();
+        assert_eq!(vault.pending_unstake_lamports, 0);
+        assert_eq!(vault.total_tvl, 101_500);
+        assert_eq!(pending.num_tickets, 0);
+        
+        // Ticket indexes are never reused
+        assert_eq!(pending.next_ticket_index, 2);
+    }
+    
+    #[test]
+    fn test_pending_unstakes_full() {
+        let data = [0u8; PendingUnstakes::LEN];
+        let mut pending = PendingUnstakes::try_deserialize_unchecked(&mut &data[..]).unwrap();
+        let mut vault = test_vault(&[]);
+        for _ in 0..MAX_PENDING_TICKETS {
+            record_unstake_ticket(&mut vault, &mut pending, ticket(1_000)).unwrap();
+        }
+        assert_eq!(
+            record_unstake_ticket(&mut vault, &mut pending, ticket(1_000)).unwrap_err(),
+            VaultError::PendingUnstakesFull.into()
+        );
+        assert_eq!(vault.pending_unstake_lamports, 1_000 * MAX_PENDING_TICKETS as u64);
+    }
 }
@@ -1,23 +1,54 @@
 //! Withdrawal instructions for the jSOL Vault
 //!
-//! Implements two-step withdrawal: request -> complete
+//! Implements two-step withdrawal: request -> complete, with cancellation
+//! of pending requests. Request accounts are keyed by a per-user nonce and
+//! closed once settled, refunding their rent to the owner.
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::clock::DEFAULT_MS_PER_SLOT;
+use anchor_lang::solana_program::epoch_schedule::EpochSchedule;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, spl_token, Burn, Mint, SyncNative, Token, TokenAccount, Transfer};
 
 use crate::constants::*;
 use crate::errors::VaultError;
 use crate::state::*;
+use crate::utils::adapter::{lamports_to_lst, lst_to_lamports};
+use crate::utils::math::calculate_bps;
+use crate::utils::protocols::get_unstaking_delay;
 
 /// Request a withdrawal from the vault
 /// 
 /// This creates a pending withdrawal request that can be completed
-/// after the unstaking period has elapsed.
+/// after the unstaking period has elapsed. The shares' jSOLi moves into
+/// the vault's withdraw escrow, so the queued claim stays fully backed.
+/// The request records the current share price and settlement policy,
+/// which decide its payout on completion.
+/// 
+/// SOL requests that idle SOL can cover are ready in the current epoch.
+/// Idle SOL already queued for other open SOL requests doesn't count, and
+/// the request's estimate is queued in turn until it settles. Otherwise
+/// they wait out the unstake delay of the slowest protocol holding vault
+/// funds, rounded up to whole epochs and never less than
+/// `UNSTAKING_EPOCHS`. The registry entry of every allocation is passed in
+/// `remaining_accounts`, in allocation order.
+/// 
+/// With a `payout_protocol`, the request settles in that protocol's LST
+/// from its custody account and can be completed immediately. The payout
+/// is quoted at the live exchange rate less `lst_payout_fee_bps`, and the
+/// request is refused if the custody balance cannot cover it. The
+/// protocol's exchange rate accounts are passed in `remaining_accounts`.
 /// 
 /// # Arguments
 /// * `ctx` - The context containing all accounts
 /// * `shares` - Number of jSOLi shares to withdraw
-pub fn request_handler(ctx: Context<RequestWithdraw>, shares: u64) -> Result<()> {
+/// * `payout_protocol` - Protocol whose LST to receive, or `None` for SOL
+pub fn request_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RequestWithdraw<'info>>,
+    shares: u64,
+    payout_protocol: Option<u8>,
+) -> Result<()> {
     require!(shares > 0, VaultError::ZeroAmount);
     
     let vault = &ctx.accounts.vault;
@@ -26,20 +57,66 @@ pub fn request_handler(ctx: Context<RequestWithdraw>, shares: u64) -> Result<()>
     // Check vault is not paused
     require!(!vault.config.is_paused, VaultError::VaultPaused);
     
+    // Shares are priced from total_tvl, so it must reflect current holdings
+    vault.require_fresh_nav(Clock::get()?.slot)?;
+    
     // Check user has enough shares
     require!(user_account.shares >= shares, VaultError::InsufficientShares);
     
     // Calculate estimated lamports value
     let estimated_lamports = vault.calculate_lamports(shares)?;
+    let share_price = vault.share_price()?;
+    let settlement_policy = vault.config.settlement_policy;
     
     // Get current timestamp and calculate ready time
     let clock = Clock::get()?;
     let now = clock.unix_timestamp;
     
-    // Unstaking takes approximately 2-3 days, we use epoch-based calculation
-    // For simplicity, using a fixed delay of 3 days (259200 seconds)
-    let unstaking_delay: i64 = 259200;
-    let ready_at = now + unstaking_delay;
+    let schedule = EpochSchedule::get()?;
+    let ready_epoch = match payout_protocol {
+        Some(protocol) => {
+            // LST payouts skip the unstake, but must be covered by custody
+            let (config, vault_lst_account) = payout_accounts(
+                &ctx.accounts.protocol_config,
+                &ctx.accounts.vault_lst_account,
+                protocol,
+            )?;
+            quote_lst_payout(vault, config, vault_lst_account, ctx.remaining_accounts, estimated_lamports)?;
+            clock.epoch
+        }
+        None => {
+            // Idle SOL above the rent-exempt minimum and not already
+            // promised to earlier requests pays out without an unstake
+            let idle_lamports = vault.spendable_idle_lamports(
+                ctx.accounts.vault_sol_account.lamports(),
+                Rent::get()?.minimum_balance(0),
+            );
+            if idle_lamports >= estimated_lamports {
+                clock.epoch
+            } else {
+                let delay_epochs = funding_delay_epochs(
+                    vault,
+                    ctx.remaining_accounts,
+                    epoch_seconds(&schedule, clock.epoch),
+                )?;
+                clock.epoch
+                    .checked_add(delay_epochs)
+                    .ok_or(VaultError::MathOverflow)?
+            }
+        }
+    };
+    let ready_at = estimate_ready_at(&clock, &schedule, ready_epoch);
+    
+    // Escrow the jSOLi tokens - user is authority of their own token account
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_jsol_account.to_account_info(),
+            to: ctx.accounts.withdraw_escrow.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, shares)?;
     
     // Initialize withdraw request
     let withdraw_request = &mut ctx.accounts.withdraw_request;
@@ -47,25 +124,33 @@ pub fn request_handler(ctx: Context<RequestWithdraw>, shares: u64) -> Result<()>
     withdraw_request.owner = ctx.accounts.user.key();
     withdraw_request.shares = shares;
     withdraw_request.estimated_lamports = estimated_lamports;
+    withdraw_request.request_share_price = share_price;
+    withdraw_request.settlement_policy = settlement_policy;
     withdraw_request.created_at = now;
     withdraw_request.ready_at = ready_at;
     withdraw_request.status = WithdrawStatus::Pending;
-    withdraw_request.request_index = user_account.pending_withdrawals as u64;
+    withdraw_request.request_index = user_account.next_request_index;
+    withdraw_request.payout_protocol = payout_protocol;
+    withdraw_request.request_epoch = clock.epoch;
+    withdraw_request.ready_epoch = ready_epoch;
     
     // Update user account
-    user_account.shares = user_account.shares
-        .checked_sub(shares)
-        .ok_or(VaultError::MathUnderflow)?;
-    user_account.pending_withdrawals = user_account.pending_withdrawals
-        .checked_add(1)
-        .ok_or(VaultError::MathOverflow)?;
-    user_account.last_activity = now;
+    user_account.queue_withdrawal(shares, now)?;
+    
+    // Hold idle SOL back for the request until it settles
+    if payout_protocol.is_none() {
+        ctx.accounts.vault.queue_withdrawal(estimated_lamports)?;
+    }
     
     // Emit event
     emit!(WithdrawRequestEvent {
         user: ctx.accounts.user.key(),
         shares,
         estimated_lamports,
+        payout_protocol,
+        share_price,
+        settlement_policy,
+        ready_epoch,
         ready_at,
         timestamp: now,
     });
@@ -73,7 +158,10 @@ pub fn request_handler(ctx: Context<RequestWithdraw>, shares: u64) -> Result<()>
     msg!("Withdrawal request created");
     msg!("Shares: {}", shares);
     msg!("Estimated lamports: {}", estimated_lamports);
-    msg!("Ready at: {}", ready_at);
+    if let Some(protocol) = payout_protocol {
+        msg!("Payout in LST of protocol {}", protocol);
+    }
+    msg!("Ready at epoch {} (estimated {})", ready_epoch, ready_at);
     
     Ok(())
 }
@@ -81,11 +169,20 @@ pub fn request_handler(ctx: Context<RequestWithdraw>, shares: u64) -> Result<()>
 /// Complete a withdrawal request
 /// 
 /// Called after the unstaking period has elapsed to actually
-/// transfer SOL to the user and burn their jSOL.
+/// transfer SOL to the user and burn their escrowed jSOL. The request account is
+/// closed and its rent refunded to the user.
+/// 
+/// The payout follows the request's settlement policy: its value at the
+/// request-time share price, at the completion-time share price, or the
+/// lower of the two. Any difference to the completion-time value stays in
+/// the vault for the remaining holders. If the user's wSOL
+/// account is passed, the payout is wrapped into it instead. Requests with
+/// a payout protocol are settled in that protocol's LST, re-quoted at the
+/// live exchange rate, with its rate accounts in `remaining_accounts`.
 /// 
 /// # Arguments
 /// * `ctx` - The context containing all accounts
-pub fn complete_handler(ctx: Context<CompleteWithdraw>) -> Result<()> {
+pub fn complete_handler<'info>(ctx: Context<'_, '_, 'info, 'info, CompleteWithdraw<'info>>) -> Result<()> {
     let vault = &mut ctx.accounts.vault;
     let withdraw_request = &mut ctx.accounts.withdraw_request;
     let user_account = &mut ctx.accounts.user_account;
@@ -105,12 +202,26 @@ pub fn complete_handler(ctx: Context<CompleteWithdraw>) -> Result<()> {
     // Check if ready
     let clock = Clock::get()?;
     require!(
-        clock.unix_timestamp >= withdraw_request.ready_at,
+        clock.epoch >= withdraw_request.ready_epoch,
         VaultError::WithdrawNotReady
     );
     
-    // Calculate actual lamports to return (recalculate in case share price changed)
-    let actual_lamports = vault.calculate_lamports(withdraw_request.shares)?;
+    // Shares are priced from total_tvl, so it must reflect current holdings
+    vault.require_fresh_nav(clock.slot)?;
+    
+    // Settle at the price chosen by the request's policy
+    let completion_lamports = vault.calculate_lamports(withdraw_request.shares)?;
+    let completion_price = vault.share_price()?;
+    let request_settles = match withdraw_request.settlement_policy {
+        SettlementPolicy::RequestPrice => true,
+        SettlementPolicy::CompletionPrice => false,
+        SettlementPolicy::LowerOfBoth => withdraw_request.estimated_lamports < completion_lamports,
+    };
+    let (settlement_price, actual_lamports) = if request_settles {
+        (withdraw_request.request_share_price, withdraw_request.estimated_lamports)
+    } else {
+        (completion_price, completion_lamports)
+    };
     
     // Check vault has sufficient liquidity
     require!(
@@ -118,61 +229,438 @@ pub fn complete_handler(ctx: Context<CompleteWithdraw>) -> Result<()> {
         VaultError::InsufficientLiquidity
     );
     
-    // Burn jSOLi tokens - user is authority of their own token account
-    let burn_ctx = CpiContext::new(
+    // SOL payouts come out of idle SOL, keeping the holding account rent-exempt
+    if withdraw_request.payout_protocol.is_none() {
+        let idle_lamports = ctx.accounts.vault_sol_account
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+        require!(idle_lamports >= actual_lamports, VaultError::InsufficientLiquidity);
+    }
+    
+    // Burn the escrowed jSOLi tokens - vault is authority of the escrow
+    let vault_seeds = &[VAULT_SEED, &[vault.bump]];
+    let signer_seeds = &[&vault_seeds[..]];
+    let burn_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
         Burn {
             mint: ctx.accounts.jsoli_mint.to_account_info(),
-            from: ctx.accounts.user_jsol_account.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
+            from: ctx.accounts.withdraw_escrow.to_account_info(),
+            authority: vault.to_account_info(),
         },
+        signer_seeds,
     );
     token::burn(burn_ctx, withdraw_request.shares)?;
     
-    // Transfer SOL from vault to user
-    **ctx.accounts.vault_sol_account.try_borrow_mut_lamports()? -= actual_lamports;
-    **ctx.accounts.user.try_borrow_mut_lamports()? += actual_lamports;
+    let lamports_received = if let Some(protocol) = withdraw_request.payout_protocol {
+        let (config, vault_lst_account) = payout_accounts(
+            &ctx.accounts.protocol_config,
+            &ctx.accounts.vault_lst_account,
+            protocol,
+        )?;
+        let user_lst_account = ctx.accounts.user_lst_account
+            .as_ref()
+            .ok_or(VaultError::MissingProtocolAccounts)?;
+        require_keys_eq!(user_lst_account.mint, config.mint, VaultError::InvalidMint);
+        
+        let quote = quote_lst_payout(vault, config, vault_lst_account, ctx.remaining_accounts, actual_lamports)?;
+        
+        // Transfer the LST from custody to the user
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: vault_lst_account.to_account_info(),
+                to: user_lst_account.to_account_info(),
+                authority: vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, quote.lst_amount)?;
+        
+        // Re-value the allocation from the remaining custody balance
+        let remaining = vault_lst_account.amount
+            .checked_sub(quote.lst_amount)
+            .ok_or(VaultError::MathUnderflow)?;
+        vault.allocations[quote.allocation_index].amount = lst_to_lamports(remaining, quote.exchange_rate)?;
+        
+        emit!(LstPayoutEvent {
+            user: ctx.accounts.user.key(),
+            protocol,
+            mint: config.mint,
+            shares: withdraw_request.shares,
+            gross_lamports: actual_lamports,
+            fee_lamports: quote.fee_lamports,
+            exchange_rate: quote.exchange_rate,
+            lst_amount: quote.lst_amount,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        // The fee stays in the vault for the remaining holders
+        actual_lamports
+            .checked_sub(quote.fee_lamports)
+            .ok_or(VaultError::MathUnderflow)?
+    } else {
+        let vault_sol_seeds = &[VAULT_SOL_SEED, &[ctx.bumps.vault_sol_account]];
+        let vault_sol_signer = &[&vault_sol_seeds[..]];
+        
+        // Pay out as wSOL if the user passed their wSOL account, else as SOL
+        if let Some(user_wsol_account) = &ctx.accounts.user_wsol_account {
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.vault_sol_account.to_account_info(),
+                    to: user_wsol_account.to_account_info(),
+                },
+                vault_sol_signer,
+            );
+            anchor_lang::system_program::transfer(transfer_ctx, actual_lamports)?;
+        
+            // Credit the wrapped lamports to the token balance
+            token::sync_native(CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                SyncNative {
+                    account: user_wsol_account.to_account_info(),
+                },
+            ))?;
+        } else {
+            // Transfer SOL from vault to user
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.vault_sol_account.to_account_info(),
+                    to: ctx.accounts.user.to_account_info(),
+                },
+                vault_sol_signer,
+            );
+            anchor_lang::system_program::transfer(transfer_ctx, actual_lamports)?;
+        }
+        
+        actual_lamports
+    };
     
     // Update vault state
     vault.total_tvl = vault.total_tvl
-        .checked_sub(actual_lamports)
+        .checked_sub(lamports_received)
         .ok_or(VaultError::MathUnderflow)?;
     vault.total_shares = vault.total_shares
         .checked_sub(withdraw_request.shares)
         .ok_or(VaultError::MathUnderflow)?;
+    vault.release_queued_withdrawal(withdraw_request);
     
     // Update user account
     user_account.total_withdrawn = user_account.total_withdrawn
-        .checked_add(actual_lamports)
+        .checked_add(lamports_received)
         .ok_or(VaultError::MathOverflow)?;
     user_account.pending_withdrawals = user_account.pending_withdrawals
         .checked_sub(1)
         .ok_or(VaultError::MathUnderflow)?;
     user_account.last_activity = clock.unix_timestamp;
     
-    // Mark request as completed
+    // Mark request as completed; the account is closed on exit
     withdraw_request.status = WithdrawStatus::Completed;
     
     // Emit event
     emit!(WithdrawCompleteEvent {
         user: ctx.accounts.user.key(),
         shares: withdraw_request.shares,
-        lamports_received: actual_lamports,
+        settlement_policy: withdraw_request.settlement_policy,
+        settlement_price,
+        lamports_received,
         timestamp: clock.unix_timestamp,
     });
     
     msg!("Withdrawal completed");
     msg!("Shares burned: {}", withdraw_request.shares);
-    msg!("Lamports received: {}", actual_lamports);
+    msg!("Settled at share price {}", settlement_price);
+    msg!("Lamports received: {}", lamports_received);
+    
+    Ok(())
+}
+
+/// Cancel a pending withdrawal request
+/// 
+/// Returns the request's escrowed jSOLi to the user and its shares to the
+/// user's position, then closes the request account, refunding its rent to
+/// the user.
+/// 
+/// Requests that don't settle at completion price had their payout fixed
+/// at request time, so cancelling one settles at the lower of the request
+/// and current value instead of handing back a free option on the share
+/// price. If the price rose, only the shares worth the request's
+/// `estimated_lamports` now are returned and the rest are burned, accruing
+/// to the remaining holders. These requests need a fresh NAV to cancel.
+/// 
+/// Cancelling is deliberately allowed while the vault is paused: it moves
+/// no SOL and leaves TVL unchanged, and it lets users take their jSOLi back
+/// out of escrow during an incident instead of waiting on the authority to
+/// unpause.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all accounts
+pub fn cancel_handler(ctx: Context<CancelWithdraw>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let withdraw_request = &mut ctx.accounts.withdraw_request;
+    let user_account = &mut ctx.accounts.user_account;
+    
+    // Check request status
+    require!(
+        withdraw_request.status == WithdrawStatus::Pending,
+        VaultError::WithdrawAlreadyProcessed
+    );
+    
+    let clock = Clock::get()?;
+    let shares = withdraw_request.shares;
+    
+    // Settle at the lower of the request and current value
+    let shares_returned = if withdraw_request.settlement_policy == SettlementPolicy::CompletionPrice {
+        shares
+    } else {
+        vault.require_fresh_nav(clock.slot)?;
+        withdraw_request.cancel_shares(vault.calculate_lamports(shares)?)?
+    };
+    let shares_burned = shares
+        .checked_sub(shares_returned)
+        .ok_or(VaultError::MathUnderflow)?;
+    
+    // Release the escrowed jSOLi tokens - vault is authority of the escrow
+    let vault_seeds = &[VAULT_SEED, &[vault.bump]];
+    let signer_seeds = &[&vault_seeds[..]];
+    if shares_returned > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.withdraw_escrow.to_account_info(),
+                to: ctx.accounts.user_jsol_account.to_account_info(),
+                authority: vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, shares_returned)?;
+    }
+    
+    // Burn the shares above the request value
+    if shares_burned > 0 {
+        let burn_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.jsoli_mint.to_account_info(),
+                from: ctx.accounts.withdraw_escrow.to_account_info(),
+                authority: vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::burn(burn_ctx, shares_burned)?;
+        
+        vault.total_shares = vault.total_shares
+            .checked_sub(shares_burned)
+            .ok_or(VaultError::MathUnderflow)?;
+    }
+    
+    // Return the shares to the user's position
+    user_account.release_withdrawal(shares_returned, clock.unix_timestamp)?;
+    vault.release_queued_withdrawal(withdraw_request);
+    
+    // Mark request as cancelled; the account is closed on exit
+    withdraw_request.status = WithdrawStatus::Cancelled;
+    
+    // Emit event
+    emit!(WithdrawCancelEvent {
+        user: ctx.accounts.user.key(),
+        shares: shares_returned,
+        shares_burned,
+        request_index: withdraw_request.request_index,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Withdrawal request cancelled");
+    msg!("Shares returned: {}", shares_returned);
+    if shares_burned > 0 {
+        msg!("Shares burned: {}", shares_burned);
+    }
+    
+    Ok(())
+}
+
+/// Close a withdrawal request left open by the legacy layout
+/// 
+/// Before requests escrowed their jSOLi, completed requests stayed open and
+/// pending ones left the jSOLi in the user's wallet, so neither can be
+/// completed or cancelled by the current instructions. This closes such an
+/// account, refunding its rent to the owner. A request that was still open
+/// also returns its shares to the user's position; its jSOLi never left
+/// the wallet. Requests in the current layout are closed as they settle and
+/// are rejected here.
+/// 
+/// # Arguments
+/// * `ctx` - The context containing all accounts
+pub fn close_request_handler(ctx: Context<CloseWithdrawRequest>) -> Result<()> {
+    let request_info = ctx.accounts.withdraw_request.to_account_info();
+    let legacy = {
+        let data = request_info.try_borrow_data()?;
+        require!(
+            data.len() == LegacyWithdrawRequest::LEN
+                && data[..8] == WithdrawRequest::DISCRIMINATOR,
+            VaultError::InvalidVaultState
+        );
+        LegacyWithdrawRequest::deserialize(&mut &data[8..])
+            .map_err(|_| VaultError::InvalidVaultState)?
+    };
+    require_keys_eq!(legacy.owner, ctx.accounts.user.key(), VaultError::Unauthorized);
+    
+    // Open requests still count against the user's position
+    let restored = matches!(legacy.status, WithdrawStatus::Pending | WithdrawStatus::Ready);
+    if restored {
+        ctx.accounts.user_account.release_withdrawal(legacy.shares, Clock::get()?.unix_timestamp)?;
+    }
+    
+    // Refund the rent and hand the account back to the system program
+    let user_info = ctx.accounts.user.to_account_info();
+    let refund = request_info.lamports();
+    **user_info.try_borrow_mut_lamports()? = user_info.lamports()
+        .checked_add(refund)
+        .ok_or(VaultError::MathOverflow)?;
+    **request_info.try_borrow_mut_lamports()? = 0;
+    request_info.assign(&anchor_lang::system_program::ID);
+    request_info.realloc(0, false)?;
+    
+    msg!("Closed legacy withdrawal request {}", legacy.request_index);
+    if restored {
+        msg!("Shares returned: {}", legacy.shares);
+    }
     
     Ok(())
 }
 
+/// Epochs until unstakes from the vault's funded protocols settle
+///
+/// `protocol_configs` holds the registry entry of every allocation, in
+/// allocation order. Each funded protocol's `unstake_delay` is rounded up to
+/// whole epochs of `epoch_seconds`; the slowest one sets the delay, which is
+/// never less than `UNSTAKING_EPOCHS`.
+pub fn funding_delay_epochs<'info>(
+    vault: &VaultState,
+    protocol_configs: &'info [AccountInfo<'info>],
+    epoch_seconds: u64,
+) -> Result<u64> {
+    let num_allocations = vault.num_allocations as usize;
+    require!(
+        protocol_configs.len() >= num_allocations,
+        VaultError::MissingProtocolAccounts
+    );
+    
+    let mut delay_epochs = UNSTAKING_EPOCHS as u64;
+    for (alloc, info) in vault.allocations[..num_allocations].iter().zip(protocol_configs) {
+        let config = Account::<ProtocolConfig>::try_from(info)?;
+        require!(config.protocol == alloc.protocol, VaultError::InvalidProtocolAccount);
+        if alloc.amount == 0 {
+            continue;
+        }
+        
+        let delay = get_unstaking_delay(&config).max(0) as u64;
+        delay_epochs = delay_epochs.max(delay.div_ceil(epoch_seconds.max(1)));
+    }
+    
+    Ok(delay_epochs)
+}
+
+/// Approximate length of `epoch` in seconds at the target slot time
+pub fn epoch_seconds(schedule: &EpochSchedule, epoch: u64) -> u64 {
+    schedule.get_slots_in_epoch(epoch).saturating_mul(DEFAULT_MS_PER_SLOT) / 1000
+}
+
+/// Estimate the unix time at which `ready_epoch` starts
+///
+/// Projects the remaining slots forward at the target slot time. Only
+/// meant for display; readiness itself is checked against the epoch.
+pub fn estimate_ready_at(clock: &Clock, schedule: &EpochSchedule, ready_epoch: u64) -> i64 {
+    if ready_epoch <= clock.epoch {
+        return clock.unix_timestamp;
+    }
+    let slots = schedule
+        .get_first_slot_in_epoch(ready_epoch)
+        .saturating_sub(clock.slot);
+    let seconds = slots.saturating_mul(DEFAULT_MS_PER_SLOT) / 1000;
+    clock.unix_timestamp.saturating_add(seconds as i64)
+}
+
+/// Settlement terms for a withdrawal paid out in a single LST
+pub struct LstPayoutQuote {
+    /// Index of the protocol's allocation in the vault
+    pub allocation_index: usize,
+    /// Live exchange rate, lamports per LST scaled by `SHARE_PRECISION`
+    pub exchange_rate: u64,
+    /// Fee withheld from the payout, in lamports
+    pub fee_lamports: u64,
+    /// LST amount to transfer to the user
+    pub lst_amount: u64,
+}
+
+/// Unwrap the optional payout accounts and check they belong to `protocol`
+pub fn payout_accounts<'a, 'info>(
+    protocol_config: &'a Option<Box<Account<'info, ProtocolConfig>>>,
+    vault_lst_account: &'a Option<Box<Account<'info, TokenAccount>>>,
+    protocol: u8,
+) -> Result<(&'a ProtocolConfig, &'a Account<'info, TokenAccount>)> {
+    let (Some(config), Some(vault_lst_account)) = (protocol_config, vault_lst_account) else {
+        return err!(VaultError::MissingProtocolAccounts);
+    };
+    require!(config.protocol == protocol, VaultError::InvalidProtocolAccount);
+    require_keys_eq!(
+        vault_lst_account.key(),
+        config.vault_token_account,
+        VaultError::InvalidProtocolAccount
+    );
+    
+    Ok((config, vault_lst_account))
+}
+
+/// Quote settling `lamports` of a withdrawal in a protocol's LST
+///
+/// Values the LST at the adapter's live exchange rate and withholds
+/// `lst_payout_fee_bps` to cover the allocation drift the payout causes.
+/// Fails if the protocol is quarantined or its custody balance cannot
+/// cover the payout.
+pub fn quote_lst_payout(
+    vault: &VaultState,
+    config: &ProtocolConfig,
+    vault_lst_account: &TokenAccount,
+    rate_accounts: &[AccountInfo],
+    lamports: u64,
+) -> Result<LstPayoutQuote> {
+    let allocation_index = vault.allocations[..vault.num_allocations as usize]
+        .iter()
+        .position(|alloc| alloc.protocol == config.protocol)
+        .ok_or(VaultError::UnsupportedProtocol)?;
+    require!(
+        !vault.allocations[allocation_index].is_quarantined,
+        VaultError::ProtocolQuarantined
+    );
+    
+    let exchange_rate = config.load_adapter().exchange_rate(rate_accounts)?;
+    let fee_lamports = calculate_bps(lamports, vault.config.lst_payout_fee_bps)?;
+    let net_lamports = lamports
+        .checked_sub(fee_lamports)
+        .ok_or(VaultError::MathUnderflow)?;
+    let lst_amount = lamports_to_lst(net_lamports, exchange_rate)?;
+    require!(lst_amount > 0, VaultError::ZeroAmount);
+    require!(
+        vault_lst_account.amount >= lst_amount,
+        VaultError::InsufficientLiquidity
+    );
+    
+    Ok(LstPayoutQuote {
+        allocation_index,
+        exchange_rate,
+        fee_lamports,
+        lst_amount,
+    })
+}
+
 #[derive(Accounts)]
 #[instruction(shares: u64)]
 pub struct RequestWithdraw<'info> {
     /// The vault state account
     #[account(
+        mut,
         seeds = [VAULT_SEED],
         bump = vault.bump
     )]
@@ -195,16 +683,59 @@ pub struct RequestWithdraw<'info> {
         seeds = [
             WITHDRAW_REQUEST_SEED,
             user.key().as_ref(),
-            &user_account.pending_withdrawals.to_le_bytes()
+            &user_account.next_request_index.to_le_bytes()
         ],
         bump
     )]
     pub withdraw_request: Account<'info, WithdrawRequest>,
     
+    /// The jSOLi token mint
+    #[account(
+        seeds = [JSOLI_MINT_SEED],
+        bump
+    )]
+    pub jsoli_mint: Box<Account<'info, Mint>>,
+    
+    /// User's jSOLi token account
+    #[account(
+        mut,
+        associated_token::mint = jsoli_mint,
+        associated_token::authority = user
+    )]
+    pub user_jsol_account: Box<Account<'info, TokenAccount>>,
+    
+    /// Vault-owned escrow holding the jSOLi of queued withdrawals
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [WITHDRAW_ESCROW_SEED],
+        bump,
+        token::mint = jsoli_mint,
+        token::authority = vault
+    )]
+    pub withdraw_escrow: Box<Account<'info, TokenAccount>>,
+    
+    /// Vault's SOL holding account, checked for idle liquidity
+    /// CHECK: This is a PDA that holds SOL
+    #[account(
+        seeds = [VAULT_SOL_SEED],
+        bump
+    )]
+    pub vault_sol_account: UncheckedAccount<'info>,
+    
+    /// Registry entry for the payout protocol, required with a payout protocol
+    pub protocol_config: Option<Box<Account<'info, ProtocolConfig>>>,
+    
+    /// Registered vault token account of the payout protocol
+    pub vault_lst_account: Option<Box<Account<'info, TokenAccount>>>,
+    
     /// The user requesting withdrawal
     #[account(mut)]
     pub user: Signer<'info>,
     
+    /// Token program
+    pub token_program: Program<'info, Token>,
+    
     /// System program
     pub system_program: Program<'info, System>,
 }
@@ -227,13 +758,13 @@ pub struct CompleteWithdraw<'info> {
     )]
     pub jsoli_mint: Account<'info, Mint>,
     
-    /// User's jSOLi token account
+    /// Vault-owned escrow holding the request's jSOLi
     #[account(
         mut,
-        associated_token::mint = jsoli_mint,
-        associated_token::authority = user
+        seeds = [WITHDRAW_ESCROW_SEED],
+        bump
     )]
-    pub user_jsol_account: Account<'info, TokenAccount>,
+    pub withdraw_escrow: Account<'info, TokenAccount>,
     
     /// User account tracking position
     #[account(
@@ -243,9 +774,10 @@ pub struct CompleteWithdraw<'info> {
     )]
     pub user_account: Account<'info, UserAccount>,
     
-    /// Withdraw request account
+    /// Withdraw request account, closed to the user
     #[account(
         mut,
+        close = user,
         constraint = withdraw_request.owner == user.key() @ VaultError::Unauthorized
     )]
     pub withdraw_request: Account<'info, WithdrawRequest>,
@@ -254,11 +786,30 @@ pub struct CompleteWithdraw<'info> {
     /// CHECK: This is a PDA that holds SOL
     #[account(
         mut,
-        seeds = [b"vault_sol"],
+        seeds = [VAULT_SOL_SEED],
         bump
     )]
     pub vault_sol_account: UncheckedAccount<'info>,
     
+    /// User's wSOL associated token account, to be paid out in wSOL
+    #[account(
+        mut,
+        address = get_associated_token_address(&user.key(), &spl_token::native_mint::ID)
+            @ VaultError::InvalidTokenAccount
+    )]
+    pub user_wsol_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Registry entry for the payout protocol, required with a payout protocol
+    pub protocol_config: Option<Box<Account<'info, ProtocolConfig>>>,
+    
+    /// Registered vault token account of the payout protocol
+    #[account(mut)]
+    pub vault_lst_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// User's token account receiving the payout LST
+    #[account(mut)]
+    pub user_lst_account: Option<Box<Account<'info, TokenAccount>>>,
+    
     /// The user completing withdrawal
     #[account(mut)]
     pub user: Signer<'info>,
@@ -269,3 +820,353 @@ pub struct CompleteWithdraw<'info> {
     /// System program
     pub system_program: Program<'info, System>,
 }
+
+#[derive(Accounts)]
+pub struct CancelWithdraw<'info> {
+    /// The vault state account, authority of the escrow
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, VaultState>,
+    
+    /// The jSOLi token mint, for burning shares a cancel doesn't return
+    #[account(
+        mut,
+        seeds = [JSOLI_MINT_SEED],
+        bump
+    )]
+    pub jsoli_mint: Account<'info, Mint>,
+    
+    /// User's jSOLi token account receiving the escrowed shares
+    #[account(
+        mut,
+        associated_token::mint = jsoli_mint,
+        associated_token::authority = user
+    )]
+    pub user_jsol_account: Account<'info, TokenAccount>,
+    
+    /// Vault-owned escrow holding the request's jSOLi
+    #[account(
+        mut,
+        seeds = [WITHDRAW_ESCROW_SEED],
+        bump
+    )]
+    pub withdraw_escrow: Account<'info, TokenAccount>,
+    
+    /// User account tracking position
+    #[account(
+        mut,
+        seeds = [USER_ACCOUNT_SEED, user.key().as_ref()],
+        bump = user_account.bump,
+        constraint = user_account.owner == user.key() @ VaultError::Unauthorized
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    
+    /// Withdraw request account to cancel, closed to the user
+    #[account(
+        mut,
+        close = user,
+        constraint = withdraw_request.owner == user.key() @ VaultError::Unauthorized
+    )]
+    pub withdraw_request: Account<'info, WithdrawRequest>,
+    
+    /// The user cancelling the withdrawal
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CloseWithdrawRequest<'info> {
+    /// User account, credited back for requests that were still open
+    #[account(
+        mut,
+        seeds = [USER_ACCOUNT_SEED, user.key().as_ref()],
+        bump = user_account.bump,
+        constraint = user_account.owner == user.key() @ VaultError::Unauthorized
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    
+    /// Legacy withdraw request account, closed to the user
+    /// CHECK: Layout, discriminator and request owner are checked in the handler
+    #[account(
+        mut,
+        owner = crate::ID @ VaultError::InvalidAccountOwner
+    )]
+    pub withdraw_request: UncheckedAccount<'info>,
+    
+    /// The owner of the request
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const EPOCH_SECONDS: u64 = 172_800;
+    
+    fn protocol_config(protocol: u8, unstake_delay: i64) -> Vec<u8> {
+        let config = ProtocolConfig {
+            bump: 0,
+            protocol,
+            adapter: AdapterKind::SplStakePool,
+            program_id: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            vault_token_account: Pubkey::new_unique(),
+            unstake_delay,
+            supports_instant_unstake: false,
+            instant_unstake_fee_bps: 0,
+            is_active: true,
+            lst_deposit_haircut_bps: 0,
+            reserved: [0; 64],
+        };
+        let mut data = Vec::new();
+        config.try_serialize(&mut data).unwrap();
+        data
+    }
+    
+    fn delay_epochs(vault: &VaultState, configs: &[(u8, i64)]) -> Result<u64> {
+        let keys: Vec<Pubkey> = configs.iter().map(|_| Pubkey::new_unique()).collect();
+        let mut lamports = vec![0u64; configs.len()];
+        let mut datas: Vec<Vec<u8>> = configs
+            .iter()
+            .map(|(protocol, delay)| protocol_config(*protocol, *delay))
+            .collect();
+        let infos: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(datas.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, &crate::ID, false, 0)
+            })
+            .collect();
+        funding_delay_epochs(vault, &infos, EPOCH_SECONDS)
+    }
+    
+    #[test]
+    fn test_epoch_seconds() {
+        // 432,000 slots at 400ms
+        let schedule = EpochSchedule::without_warmup();
+        assert_eq!(epoch_seconds(&schedule, 500), EPOCH_SECONDS);
+        
+        // Warmup epochs are shorter
+        let warmup = EpochSchedule::custom(432_000, 432_000, true);
+        assert!(epoch_seconds(&warmup, 0) < epoch_seconds(&warmup, 500));
+    }
+    
+    #[test]
+    fn test_funding_delay_epochs() {
+        // No delay configured still waits UNSTAKING_EPOCHS
+        let funded = test_vault(&[(0, 100), (1, 100)]);
+        assert_eq!(delay_epochs(&funded, &[(0, 0), (1, 0)]).unwrap(), UNSTAKING_EPOCHS as u64);
+        
+        // The slowest funded protocol sets the delay, rounded up
+        let delays = [(0, EPOCH_SECONDS as i64), (1, 2 * EPOCH_SECONDS as i64 + 1)];
+        assert_eq!(delay_epochs(&funded, &delays).unwrap(), 3);
+        
+        // Unfunded protocols don't hold requests back
+        let partly_funded = test_vault(&[(0, 100), (1, 0)]);
+        assert_eq!(delay_epochs(&partly_funded, &delays).unwrap(), 1);
+    }
+    
+    #[test]
+    fn test_funding_delay_epochs_checks_registry() {
+        let vault = test_vault(&[(0, 100), (1, 100)]);
+        
+        // Every allocation's entry is required
+        assert_eq!(
+            delay_epochs(&vault, &[(0, 0)]).unwrap_err(),
+            VaultError::MissingProtocolAccounts.into()
+        );
+        
+        // Entries must be in allocation order
+        assert_eq!(
+            delay_epochs(&vault, &[(1, 0), (0, 0)]).unwrap_err(),
+            VaultError::InvalidProtocolAccount.into()
+        );
+    }
+    
+    #[test]
+    fn test_release_queued_withdrawal() {
+        let mut vault = test_vault(&[]);
+        vault.queued_withdraw_lamports = 5_000;
+        
+        let data = [0u8; WithdrawRequest::LEN];
+        let mut request = WithdrawRequest::try_deserialize_unchecked(&mut &data[..]).unwrap();
+        request.estimated_lamports = 3_000;
+        
+        // LST requests never queued idle SOL
+        request.payout_protocol = Some(0);
+        vault.release_queued_withdrawal(&request);
+        assert_eq!(vault.queued_withdraw_lamports, 5_000);
+        
+        request.payout_protocol = None;
+        vault.release_queued_withdrawal(&request);
+        assert_eq!(vault.queued_withdraw_lamports, 2_000);
+        
+        // Requests opened before the queue was tracked release nothing extra
+        vault.release_queued_withdrawal(&request);
+        assert_eq!(vault.queued_withdraw_lamports, 0);
+    }
+    
+    #[test]
+    fn test_ready_request_holds_idle_sol() {
+        const RENT: u64 = 890_880;
+        let mut vault = test_vault(&[]);
+        vault.total_tvl = 10_000_000_000;
+        vault.total_shares = 10_000_000_000;
+        let balance = RENT + 4_000_000_000;
+        assert_eq!(vault.spendable_idle_lamports(balance, RENT), 4_000_000_000);
+    
+        // A request for 3 SOL of idle SOL is queued until it completes
+        vault.queue_withdrawal(3_000_000_000).unwrap();
+        let spendable = vault.spendable_idle_lamports(balance, RENT);
+        assert_eq!(spendable, 1_000_000_000);
+    
+        // Staking the previous idle balance is no longer possible
+        assert!(4_000_000_000 > spendable);
+    
+        // An in-kind redemption of 10% takes 10% of the spendable idle SOL only
+        let idle_slice = crate::utils::math::calculate_value_for_shares(1_000_000_000, spendable, vault.total_shares).unwrap();
+        assert_eq!(idle_slice, 100_000_000);
+    
+        // Completing the request releases the queue with the payout
+        let mut request = request(3_000_000_000, 3_000_000_000, SettlementPolicy::RequestPrice);
+        request.payout_protocol = None;
+        vault.release_queued_withdrawal(&request);
+        let balance = balance - 3_000_000_000;
+        assert_eq!(vault.spendable_idle_lamports(balance, RENT), 1_000_000_000);
+    
+        // The holding account never drops below its rent-exempt minimum
+        assert_eq!(vault.spendable_idle_lamports(RENT - 1, RENT), 0);
+    }
+    
+    #[test]
+    fn test_legacy_request_layout() {
+        let legacy = LegacyWithdrawRequest {
+            bump: 255,
+            owner: Pubkey::new_unique(),
+            shares: 1_000,
+            estimated_lamports: 1_000,
+            created_at: 1,
+            ready_at: 2,
+            status: WithdrawStatus::Pending,
+            request_index: 0,
+            reserved: [0; 32],
+        };
+        let data = legacy.try_to_vec().unwrap();
+        assert_eq!(data.len() + 8, LegacyWithdrawRequest::LEN);
+        
+        // Current requests can never be mistaken for legacy ones
+        assert_ne!(WithdrawRequest::LEN, LegacyWithdrawRequest::LEN);
+    }
+    
+    fn request(shares: u64, estimated_lamports: u64, policy: SettlementPolicy) -> WithdrawRequest {
+        let data = [0u8; WithdrawRequest::LEN];
+        let mut request = WithdrawRequest::try_deserialize_unchecked(&mut &data[..]).unwrap();
+        request.shares = shares;
+        request.estimated_lamports = estimated_lamports;
+        request.settlement_policy = policy;
+        request
+    }
+    
+    #[test]
+    fn test_cancel_after_price_rise_burns_excess() {
+        // 10 of 100 shares requested at 1 SOL each, then the price rises 25%
+        let mut user_account = user_account(100);
+        user_account.queue_withdrawal(10, 10).unwrap();
+        let mut vault = test_vault(&[]);
+        vault.total_tvl = 125;
+        vault.total_shares = 100;
+        let completion_lamports = vault.calculate_lamports(10).unwrap();
+        
+        for policy in [SettlementPolicy::RequestPrice, SettlementPolicy::LowerOfBoth] {
+            // Only the shares worth the request value come back
+            let returned = request(10, 10, policy).cancel_shares(completion_lamports).unwrap();
+            assert_eq!(returned, 8);
+            assert_eq!(vault.calculate_lamports(returned).unwrap(), 10);
+        }
+        
+        // The position only regains the returned shares
+        user_account.release_withdrawal(8, 20).unwrap();
+        assert_eq!(user_account.shares, 98);
+        assert_eq!(user_account.pending_withdrawals, 0);
+        
+        // Completion price requests carry no fixed claim
+        let returned = request(10, 10, SettlementPolicy::CompletionPrice)
+            .cancel_shares(completion_lamports)
+            .unwrap();
+        assert_eq!(returned, 10);
+    }
+    
+    #[test]
+    fn test_cancel_after_price_fall_returns_all() {
+        // 10 of 100 shares requested at 1 SOL each, then the price falls 20%
+        let mut vault = test_vault(&[]);
+        vault.total_tvl = 80;
+        vault.total_shares = 100;
+        let completion_lamports = vault.calculate_lamports(10).unwrap();
+        
+        for policy in [
+            SettlementPolicy::RequestPrice,
+            SettlementPolicy::CompletionPrice,
+            SettlementPolicy::LowerOfBoth,
+        ] {
+            let returned = request(10, 10, policy).cancel_shares(completion_lamports).unwrap();
+            assert_eq!(returned, 10);
+        }
+    }
+    
+    fn user_account(shares: u64) -> UserAccount {
+        UserAccount {
+            bump: 0,
+            owner: Pubkey::new_unique(),
+            shares,
+            total_deposited: shares,
+            total_withdrawn: 0,
+            first_deposit: 1,
+            last_activity: 1,
+            pending_withdrawals: 0,
+            next_request_index: 0,
+            reserved: [0; 56],
+        }
+    }
+    
+    #[test]
+    fn test_request_then_cancel_restores_position() {
+        let mut user_account = user_account(1_000);
+        
+        user_account.queue_withdrawal(400, 10).unwrap();
+        assert_eq!(user_account.shares, 600);
+        assert_eq!(user_account.pending_withdrawals, 1);
+        
+        user_account.release_withdrawal(400, 20).unwrap();
+        assert_eq!(user_account.shares, 1_000);
+        assert_eq!(user_account.pending_withdrawals, 0);
+        assert_eq!(user_account.last_activity, 20);
+        
+        // Request indexes are never reused
+        assert_eq!(user_account.next_request_index, 1);
+    }
+    
+    #[test]
+    fn test_queue_withdrawal_rejects_excess_shares() {
+        let mut user_account = user_account(1_000);
+        assert_eq!(
+            user_account.queue_withdrawal(1_001, 10).unwrap_err(),
+            VaultError::InsufficientShares.into()
+        );
+        
+        // Releasing without a pending request underflows
+        assert_eq!(
+            user_account.release_withdrawal(1, 10).unwrap_err(),
+            VaultError::MathUnderflow.into()
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! Stake account withdrawal instruction
+//!
+//! Lets holders exit into a stake account split from an SPL pool
+//! allocation, skipping the unstake queue and the pool's SOL withdrawal fee.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::stake::{self, state::StakeStateV2};
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+
+use crate::constants::*;
+use crate::errors::VaultError;
+use crate::state::*;
+use crate::utils::adapter::{
+    adapter_withdraw_stake, check_accounts, custody_value, AdapterOperation, VaultAccounts,
+};
+
+/// Redeem jSOLi for a stake account
+///
+/// Burns `shares` and redeems their value in the protocol's LST for stake
+/// through the pool's `withdraw_stake`. The stake lands in `stake_account`,
+/// a fresh keypair allocated here at the user's expense, with the user as
+/// staker and withdrawer. The user can deactivate and withdraw it on their
+/// own schedule. Any pool stake withdrawal fee comes out of the stake
+/// received.
+///
+/// Protocol-specific accounts are passed in `remaining_accounts`, in the
+/// order documented by the protocol's `LstAdapter` implementation.
+///
+/// # Arguments
+/// * `ctx` - The context containing all accounts
+/// * `protocol` - The SPL pool protocol to withdraw stake from
+/// * `shares` - Number of jSOLi shares to redeem
+pub fn withdraw_stake_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawStake<'info>>,
+    protocol: u8,
+    shares: u64,
+) -> Result<()> {
+    require!(shares > 0, VaultError::ZeroAmount);
+    
+    let clock = Clock::get()?;
+    let vault = &ctx.accounts.vault;
+    
+    // Check vault is not paused
+    require!(!vault.config.is_paused, VaultError::VaultPaused);
+    
+    // Shares are priced from total_tvl, so it must reflect current holdings
+    vault.require_fresh_nav(clock.slot)?;
+    
+    // Check user has enough shares
+    require!(
+        ctx.accounts.user_account.shares >= shares,
+        VaultError::InsufficientShares
+    );
+    
+    // Validate the source protocol
+    let protocol_index = vault.allocations[..vault.num_allocations as usize]
+        .iter()
+        .position(|alloc| alloc.protocol == protocol)
+        .ok_or(VaultError::UnsupportedProtocol)?;
+    require!(
+        !vault.allocations[protocol_index].is_quarantined,
+        VaultError::ProtocolQuarantined
+    );
+    
+    let lamports = vault.calculate_lamports(shares)?;
+    require!(
+        vault.total_tvl >= lamports,
+        VaultError::InsufficientLiquidity
+    );
+    
+    // Only protocols that read accounts for it support stake withdrawals
+    let adapter = ctx.accounts.protocol_config.load_adapter();
+    check_accounts(adapter.as_ref(), AdapterOperation::WithdrawStake, ctx.remaining_accounts)?;
+    
+    // Allocate the stake account that receives the split
+    let space = StakeStateV2::size_of();
+    let create_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        CreateAccount {
+            from: ctx.accounts.user.to_account_info(),
+            to: ctx.accounts.stake_account.to_account_info(),
+        },
+    );
+    system_program::create_account(
+        create_ctx,
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &stake::program::ID,
+    )?;
+    
+    // Redeem the LST for stake owned by the user
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault_sol_info = ctx.accounts.vault_sol_account.to_account_info();
+    let vault_token_info = ctx.accounts.vault_lst_account.to_account_info();
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+    let token_program_info = ctx.accounts.token_program.to_account_info();
+    let vault_accounts = VaultAccounts {
+        vault: &vault_info,
+        vault_sol: &vault_sol_info,
+        vault_token_account: &vault_token_info,
+        system_program: &system_program_info,
+        token_program: &token_program_info,
+        vault_bump: ctx.accounts.vault.bump,
+        vault_sol_bump: ctx.bumps.vault_sol_account,
+    };
+    let (lst_amount, stake_lamports) = adapter_withdraw_stake(
+        adapter.as_ref(),
+        &vault_accounts,
+        &ctx.accounts.stake_account.to_account_info(),
+        &ctx.accounts.user.to_account_info(),
+        ctx.remaining_accounts,
+        lamports,
+    )?;
+    let custody = custody_value(adapter.as_ref(), &vault_token_info, ctx.remaining_accounts)?;
+    
+    // Burn jSOLi tokens - user is authority of their own token account
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.jsoli_mint.to_account_info(),
+            from: ctx.accounts.user_jsol_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::burn(burn_ctx, shares)?;
+    
+    // Update vault state
+    settle_stake_withdrawal(&mut ctx.accounts.vault, protocol_index, shares, lamports, custody)?;
+    
+    // Update user account
+    let user_account = &mut ctx.accounts.user_account;
+    user_account.shares = user_account.shares
+        .checked_sub(shares)
+        .ok_or(VaultError::MathUnderflow)?;
+    user_account.total_withdrawn = user_account.total_withdrawn
+        .checked_add(lamports)
+        .ok_or(VaultError::MathOverflow)?;
+    user_account.last_activity = clock.unix_timestamp;
+    
+    emit!(StakeWithdrawEvent {
+        user: ctx.accounts.user.key(),
+        stake_account: ctx.accounts.stake_account.key(),
+        protocol,
+        shares,
+        lamports,
+        lst_amount,
+        stake_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Stake withdrawal completed");
+    msg!("Shares burned: {}", shares);
+    msg!("Redeemed {} LST worth {} lamports", lst_amount, lamports);
+    msg!("Stake received: {} lamports", stake_lamports);
+    
+    Ok(())
+}
+
+/// Remove a stake withdrawal from the vault
+/// 
+/// Burns `shares` against the `lamports` they were valued at and records
+/// the protocol's remaining `custody` value.
+pub fn settle_stake_withdrawal(
+    vault: &mut VaultState,
+    protocol_index: usize,
+    shares: u64,
+    lamports: u64,
+    custody: u64,
+) -> Result<()> {
+    vault.total_tvl = vault.total_tvl
+        .checked_sub(lamports)
+        .ok_or(VaultError::MathUnderflow)?;
+    vault.total_shares = vault.total_shares
+        .checked_sub(shares)
+        .ok_or(VaultError::MathUnderflow)?;
+    vault.allocations[protocol_index].amount = custody;
+    vault.update_current_bps()
+}
+
+#[derive(Accounts)]
+#[instruction(protocol: u8)]
+pub struct WithdrawStake<'info> {
+    /// The vault state account
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Box<Account<'info, VaultState>>,
+    
+    /// The jSOLi token mint
+    #[account(
+        mut,
+        seeds = [JSOLI_MINT_SEED],
+        bump
+    )]
+    pub jsoli_mint: Box<Account<'info, Mint>>,
+    
+    /// User's jSOLi token account
+    #[account(
+        mut,
+        associated_token::mint = jsoli_mint,
+        associated_token::authority = user
+    )]
+    pub user_jsol_account: Box<Account<'info, TokenAccount>>,
+    
+    /// User account tracking position
+    #[account(
+        mut,
+        seeds = [USER_ACCOUNT_SEED, user.key().as_ref()],
+        bump = user_account.bump,
+        constraint = user_account.owner == user.key() @ VaultError::Unauthorized
+    )]
+    pub user_account: Box<Account<'info, UserAccount>>,
+    
+    /// New stake account receiving the withdrawal
+    #[account(mut)]
+    pub stake_account: Signer<'info>,
+    
+    /// Registry entry for the protocol
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED, &[protocol]],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+    
+    /// Registered vault token account holding the pool tokens
+    #[account(
+        mut,
+        address = protocol_config.vault_token_account @ VaultError::InvalidProtocolAccount
+    )]
+    pub vault_lst_account: Box<Account<'info, TokenAccount>>,
+    
+    /// Vault's SOL holding account
+    /// CHECK: This is a PDA that holds SOL
+    #[account(
+        seeds = [VAULT_SOL_SEED],
+        bump
+    )]
+    pub vault_sol_account: UncheckedAccount<'info>,
+    
+    /// The user withdrawing, new staker and withdrawer of the stake account
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// System program
+    pub system_program: Program<'info, System>,
+    
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{LstAdapter, MarinadeAdapter, SplStakePoolAdapter};
+    
+    const SOL: u64 = 1_000_000_000;
+    
+    #[test]
+    fn test_stake_withdrawal_accounting() {
+        // 110 SOL against 100 shares, 66 SOL of it in protocol 1
+        let mut vault = test_vault(&[(0, 44 * SOL), (1, 66 * SOL)]);
+        vault.total_tvl = 110 * SOL;
+        vault.total_shares = 100 * SOL;
+        let price_before = vault.share_price().unwrap();
+        
+        // 10 shares are worth 11 SOL, all redeemed out of protocol 1
+        let shares = 10 * SOL;
+        let lamports = vault.calculate_lamports(shares).unwrap();
+        assert_eq!(lamports, 11 * SOL);
+        
+        settle_stake_withdrawal(&mut vault, 1, shares, lamports, 55 * SOL).unwrap();
+        assert_eq!(vault.total_tvl, 99 * SOL);
+        assert_eq!(vault.total_shares, 90 * SOL);
+        assert_eq!(vault.allocations[0].amount, 44 * SOL);
+        assert_eq!(vault.allocations[1].amount, 55 * SOL);
+        assert_eq!(vault.share_price().unwrap(), price_before);
+        
+        // Shares can't be burned beyond the supply
+        assert_eq!(
+            settle_stake_withdrawal(&mut vault, 1, 91 * SOL, SOL, 0).unwrap_err(),
+            VaultError::MathUnderflow.into()
+        );
+    }
+    
+    #[test]
+    fn test_withdraw_stake_rejects_marinade() {
+        let key = Pubkey::new_unique();
+        let marinade = MarinadeAdapter::new(key, key, key);
+        assert_eq!(marinade.required_accounts(AdapterOperation::WithdrawStake), 0);
+        assert_eq!(
+            check_accounts(&marinade, AdapterOperation::WithdrawStake, &[]).unwrap_err(),
+            VaultError::UnsupportedProtocol.into()
+        );
+        
+        // SPL pools support it, given their accounts
+        let pool = SplStakePoolAdapter::new(key, key, key);
+        assert!(pool.required_accounts(AdapterOperation::WithdrawStake) > 0);
+        assert_eq!(
+            check_accounts(&pool, AdapterOperation::WithdrawStake, &[]).unwrap_err(),
+            VaultError::MissingProtocolAccounts.into()
+        );
+    }
+}
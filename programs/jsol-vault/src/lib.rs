@@ -37,25 +37,104 @@ pub mod jsol_vault {
     /// Deposit SOL into the vault
     ///
     /// Mints jSOLi shares to the depositor based on current share price.
-    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+    pub fn deposit<'info>(ctx: Context<'_, '_, 'info, 'info, Deposit<'info>>, amount: u64) -> Result<()> {
         deposit_handler(ctx, amount)
     }
 
+    /// Deposit a delegated stake account into the vault
+    ///
+    /// Mints jSOLi shares for the value of the stake once deposited into
+    /// the protocol's stake pool.
+    pub fn deposit_stake<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DepositStake<'info>>,
+        protocol: u8,
+    ) -> Result<()> {
+        deposit_stake_handler(ctx, protocol)
+    }
+
+    /// Deposit a supported LST into the vault
+    ///
+    /// Mints jSOLi shares for the LST's value at the live exchange rate,
+    /// less the protocol's deposit haircut.
+    pub fn deposit_lst<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DepositLst<'info>>,
+        protocol: u8,
+        amount: u64,
+    ) -> Result<()> {
+        deposit_lst_handler(ctx, protocol, amount)
+    }
+
+    /// Deposit wSOL into the vault
+    ///
+    /// Unwraps the wSOL into the vault and mints jSOLi shares exactly as a
+    /// native SOL deposit would.
+    pub fn deposit_wsol<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DepositWsol<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        deposit_wsol_handler(ctx, amount)
+    }
+
     /// Request a withdrawal from the vault
-    pub fn request_withdraw(ctx: Context<RequestWithdraw>, shares: u64) -> Result<()> {
-        withdraw::request_handler(ctx, shares)
+    ///
+    /// With a payout protocol, the request settles in that protocol's LST
+    /// without waiting for an unstake.
+    pub fn request_withdraw<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RequestWithdraw<'info>>,
+        shares: u64,
+        payout_protocol: Option<u8>,
+    ) -> Result<()> {
+        withdraw::request_handler(ctx, shares, payout_protocol)
     }
 
     /// Complete a pending withdrawal
-    pub fn complete_withdraw(ctx: Context<CompleteWithdraw>) -> Result<()> {
+    pub fn complete_withdraw<'info>(ctx: Context<'_, '_, 'info, 'info, CompleteWithdraw<'info>>) -> Result<()> {
         withdraw::complete_handler(ctx)
     }
+    
+    /// Cancel a pending withdrawal and return its shares
+    pub fn cancel_withdraw(ctx: Context<CancelWithdraw>) -> Result<()> {
+        withdraw::cancel_handler(ctx)
+    }
+    
+    /// Close a withdrawal request left open by the legacy layout
+    pub fn close_withdraw_request(ctx: Context<CloseWithdrawRequest>) -> Result<()> {
+        withdraw::close_request_handler(ctx)
+    }
+
+    /// Redeem jSOLi for a pro-rata slice of the vault's assets
+    ///
+    /// Pays out each LST custody balance and idle SOL immediately, without
+    /// going through the unstake queue.
+    pub fn redeem_in_kind<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RedeemInKind<'info>>,
+        shares: u64,
+    ) -> Result<()> {
+        redeem_in_kind_handler(ctx, shares)
+    }
+
+    /// Redeem jSOLi for a stake account from an SPL pool allocation
+    ///
+    /// The user becomes staker and withdrawer of the new stake account,
+    /// skipping the unstake queue and the pool's SOL withdrawal fee.
+    pub fn withdraw_stake<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawStake<'info>>,
+        protocol: u8,
+        shares: u64,
+    ) -> Result<()> {
+        withdraw_stake_handler(ctx, protocol, shares)
+    }
 
     /// Rebalance vault allocations
-    pub fn rebalance(ctx: Context<Rebalance>) -> Result<()> {
+    pub fn rebalance<'info>(ctx: Context<'_, '_, 'info, 'info, Rebalance<'info>>) -> Result<()> {
         rebalance_handler(ctx)
     }
 
+    /// Refresh the vault NAV from its holdings (permissionless)
+    pub fn update_nav<'info>(ctx: Context<'_, '_, 'info, 'info, UpdateNav<'info>>) -> Result<()> {
+        update_nav_handler(ctx)
+    }
+
     /// Update vault configuration
     pub fn update_config(
         ctx: Context<AdminAction>,
@@ -77,6 +156,42 @@ pub mod jsol_vault {
         admin::collect_fees_handler(ctx)
     }
 
+    /// Register a protocol in the registry
+    pub fn register_protocol(
+        ctx: Context<RegisterProtocol>,
+        protocol: u8,
+        params: RegisterProtocolParams,
+    ) -> Result<()> {
+        admin::register_protocol_handler(ctx, protocol, params)
+    }
+
+    /// Update a protocol registry entry
+    pub fn update_protocol(
+        ctx: Context<UpdateProtocol>,
+        params: UpdateProtocolParams,
+    ) -> Result<()> {
+        admin::update_protocol_handler(ctx, params)
+    }
+
+    /// Configure a Pyth price feed
+    pub fn set_oracle_feed(
+        ctx: Context<SetOracleFeed>,
+        feed_id: [u8; 32],
+        params: SetOracleFeedParams,
+    ) -> Result<()> {
+        admin::set_oracle_feed_handler(ctx, feed_id, params)
+    }
+
+    /// Release a protocol quarantined by the exchange rate guard
+    pub fn clear_quarantine(ctx: Context<AdminAction>, protocol: u8) -> Result<()> {
+        admin::clear_quarantine_handler(ctx, protocol)
+    }
+
+    /// Migrate the vault account from the legacy layout
+    pub fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+        admin::migrate_vault_handler(ctx)
+    }
+
     /// Pause the vault
     pub fn pause(ctx: Context<AdminAction>) -> Result<()> {
         admin::pause_handler(ctx)
@@ -88,8 +203,8 @@ pub mod jsol_vault {
     }
 
     /// Stake SOL to a specific protocol
-    pub fn stake_to_protocol(
-        ctx: Context<StakeToProtocol>,
+    pub fn stake_to_protocol<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StakeToProtocol<'info>>,
         protocol: u8,
         amount: u64,
     ) -> Result<()> {
@@ -97,11 +212,28 @@ pub mod jsol_vault {
     }
 
     /// Unstake from a specific protocol
-    pub fn unstake_from_protocol(
-        ctx: Context<UnstakeFromProtocol>,
+    pub fn unstake_from_protocol<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UnstakeFromProtocol<'info>>,
         protocol: u8,
         amount: u64,
     ) -> Result<()> {
         staking::unstake_handler(ctx, protocol, amount)
     }
+
+    /// Instantly unstake from a specific LST protocol
+    pub fn instant_unstake<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InstantUnstake<'info>>,
+        protocol: u8,
+        amount: u64,
+    ) -> Result<()> {
+        staking::instant_unstake_handler(ctx, protocol, amount)
+    }
+    
+    /// Claim a matured delayed-unstake ticket into the vault
+    pub fn claim_unstake<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimUnstake<'info>>,
+        protocol: u8,
+    ) -> Result<()> {
+        staking::claim_unstake_handler(ctx, protocol)
+    }
 }
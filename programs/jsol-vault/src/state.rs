@@ -22,6 +22,49 @@ pub enum LstProtocol {
     Jupiter,
 }
 
+/// Integration adapter used by a registered protocol
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AdapterKind {
+    /// SPL stake pool (jitoSOL, bSOL, JupSOL, Sanctum LSTs)
+    SplStakePool,
+    /// Marinade Finance (mSOL)
+    Marinade,
+}
+
+/// Price provider behind an oracle source
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum OracleProviderKind {
+    /// Pyth pull oracle (`PriceUpdateV2` accounts)
+    #[default]
+    Pyth,
+    /// Switchboard On-Demand pull feed
+    Switchboard,
+    /// Fixed-layout price account for localnet (requires `mock-oracle`)
+    Mock,
+}
+
+/// How prices from several oracle sources are combined
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum OracleAggregation {
+    /// First source, in configured order, that returns a valid price
+    #[default]
+    Fallback,
+    /// Median of every source that returns a valid price
+    Median,
+}
+
+/// Share price a queued SOL withdrawal settles at
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SettlementPolicy {
+    /// Share price when the request was made
+    RequestPrice,
+    /// Share price when the request is completed
+    CompletionPrice,
+    /// Lower of the request and completion share prices
+    #[default]
+    LowerOfBoth,
+}
+
 /// Status of a withdrawal request
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
 pub enum WithdrawStatus {
@@ -44,18 +87,26 @@ pub enum WithdrawStatus {
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
 pub struct Allocation {
     /// The LST protocol
-    pub protocol: u8, // Stored as u8 for space efficiency, maps to LstProtocol
+    pub protocol: u8, // Stored as u8 for space efficiency, keys the ProtocolConfig registry
     /// Target allocation in basis points (0-10000)
     pub target_bps: u16,
     /// Current actual allocation in basis points
     pub current_bps: u16,
     /// Amount of SOL value in this protocol
     pub amount: u64,
+    /// Last accepted LST exchange rate (scaled by SHARE_PRECISION, 0 = none)
+    pub last_rate: u64,
+    /// Slot the last rate was accepted at
+    pub last_rate_slot: u64,
+    /// Epoch the last rate was accepted at
+    pub last_rate_epoch: u64,
+    /// Set when the rate jumped past the guard; cleared by the authority
+    pub is_quarantined: bool,
 }
 
 impl Allocation {
     /// Size of Allocation struct in bytes
-    pub const LEN: usize = 1 + 2 + 2 + 8; // 13 bytes
+    pub const LEN: usize = 1 + 2 + 2 + 8 + 8 + 8 + 8 + 1; // 38 bytes
 }
 
 /// Vault configuration parameters
@@ -73,6 +124,16 @@ pub struct VaultConfig {
     pub deposit_cap: u64,
     /// Whether the vault is paused
     pub is_paused: bool,
+    /// Maximum NAV age in slots for deposits and withdrawals (0 = unchecked)
+    pub max_nav_staleness_slots: u64,
+    /// Maximum LST exchange rate move per epoch in basis points (0 = unchecked)
+    pub max_rate_change_bps_per_epoch: u16,
+    /// Maximum oracle confidence interval as basis points of price (0 = unchecked)
+    pub max_confidence_bps: u16,
+    /// Fee on withdrawals paid out in a single LST, in basis points
+    pub lst_payout_fee_bps: u16,
+    /// Share price queued withdrawals settle at
+    pub settlement_policy: SettlementPolicy,
 }
 
 impl Default for VaultConfig {
@@ -84,13 +145,49 @@ impl Default for VaultConfig {
             max_slippage_bps: 100, // 1% default slippage
             deposit_cap: 0,        // No cap by default
             is_paused: false,
+            max_nav_staleness_slots: DEFAULT_MAX_NAV_STALENESS_SLOTS,
+            max_rate_change_bps_per_epoch: DEFAULT_MAX_RATE_CHANGE_BPS_PER_EPOCH,
+            max_confidence_bps: DEFAULT_MAX_CONFIDENCE_BPS,
+            lst_payout_fee_bps: DEFAULT_LST_PAYOUT_FEE_BPS,
+            settlement_policy: SettlementPolicy::default(),
         }
     }
 }
 
 impl VaultConfig {
     /// Size of VaultConfig struct in bytes
-    pub const LEN: usize = 2 + 2 + 2 + 2 + 8 + 1; // 17 bytes
+    pub const LEN: usize = 2 + 2 + 2 + 2 + 8 + 1 + 8 + 2 + 2 + 2 + 1; // 32 bytes
+}
+
+/// A delayed-unstake ticket awaiting its claim
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PendingTicket {
+    /// Ticket account, a vault PDA owned by the protocol program
+    pub ticket: Pubkey,
+    /// LST amount burned to open the ticket
+    pub lst_amount: u64,
+    /// Lamports the ticket pays out on claim
+    pub lamports: u64,
+    /// Epoch the ticket was opened in
+    pub created_epoch: u64,
+}
+
+impl PendingTicket {
+    /// Size of PendingTicket in bytes
+    pub const LEN: usize = 32 + 8 + 8 + 8; // 56 bytes
+}
+
+/// One LST leg of an in-kind redemption
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RedeemedAsset {
+    /// Protocol whose custody account paid out
+    pub protocol: u8,
+    /// LST mint
+    pub mint: Pubkey,
+    /// LST amount transferred, in token base units
+    pub amount: u64,
+    /// Lamport value of the amount at the allocation's last valuation
+    pub value: u64,
 }
 
 // ============================================================================
@@ -148,8 +245,17 @@ pub struct VaultState {
     /// Protocol allocations (fixed size array)
     pub allocations: [Allocation; MAX_PROTOCOLS],
     
+    /// Slot of the last NAV refresh
+    pub last_nav_slot: u64,
+    
+    /// Lamports owed by open delayed-unstake tickets, counted in NAV
+    pub pending_unstake_lamports: u64,
+    
+    /// Idle SOL promised to open SOL withdrawal requests, at request price
+    pub queued_withdraw_lamports: u64,
+    
     /// Reserved space for future upgrades
-    pub reserved: [u8; 128],
+    pub reserved: [u8; 120],
 }
 
 impl VaultState {
@@ -170,7 +276,10 @@ impl VaultState {
         VaultConfig::LEN +     // config
         1 +                    // num_allocations
         (Allocation::LEN * MAX_PROTOCOLS) + // allocations
-        128;                   // reserved
+        8 +                    // last_nav_slot
+        8 +                    // pending_unstake_lamports
+        8 +                    // queued_withdraw_lamports
+        120;                   // reserved
     
     /// Calculate the current share price (value per share in lamports)
     /// Returns error on overflow
@@ -211,6 +320,66 @@ impl VaultState {
         }
     }
     
+    /// Idle SOL the vault can spend without touching queued withdrawals
+    /// 
+    /// The vault SOL account's balance above `rent_exempt_minimum`, less the
+    /// idle SOL queued for open SOL withdrawal requests.
+    pub fn spendable_idle_lamports(&self, vault_sol_lamports: u64, rent_exempt_minimum: u64) -> u64 {
+        vault_sol_lamports
+            .saturating_sub(rent_exempt_minimum)
+            .saturating_sub(self.queued_withdraw_lamports)
+    }
+    
+    /// Queue idle SOL for a new SOL withdrawal request
+    pub fn queue_withdrawal(&mut self, lamports: u64) -> Result<()> {
+        use crate::errors::VaultError;
+        self.queued_withdraw_lamports = self.queued_withdraw_lamports
+            .checked_add(lamports)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(())
+    }
+    
+    /// Release the idle SOL queued for a settled SOL withdrawal request
+    /// 
+    /// Saturates, since requests opened before the queue was tracked were
+    /// never added to it.
+    pub fn release_queued_withdrawal(&mut self, request: &WithdrawRequest) {
+        if request.payout_protocol.is_none() {
+            self.queued_withdraw_lamports = self.queued_withdraw_lamports
+                .saturating_sub(request.estimated_lamports);
+        }
+    }
+    
+    /// Require the NAV to have been refreshed within the configured slots
+    pub fn require_fresh_nav(&self, current_slot: u64) -> Result<()> {
+        use crate::errors::VaultError;
+        if self.config.max_nav_staleness_slots == 0 {
+            return Ok(());
+        }
+        let age = current_slot.saturating_sub(self.last_nav_slot);
+        require!(
+            age <= self.config.max_nav_staleness_slots,
+            VaultError::StaleNav
+        );
+        Ok(())
+    }
+    
+    /// Recalculate each allocation's `current_bps` from its amount and TVL
+    pub fn update_current_bps(&mut self) -> Result<()> {
+        use crate::errors::VaultError;
+        if self.total_tvl == 0 {
+            return Ok(());
+        }
+        for i in 0..self.num_allocations as usize {
+            self.allocations[i].current_bps = ((self.allocations[i].amount as u128)
+                .checked_mul(TOTAL_ALLOCATION_BPS as u128)
+                .ok_or(VaultError::MathOverflow)?
+                .checked_div(self.total_tvl as u128)
+                .ok_or(VaultError::DivisionByZero)?) as u16;
+        }
+        Ok(())
+    }
+    
     /// Calculate lamports value for a given share amount
     pub fn calculate_lamports(&self, shares: u64) -> Result<u64> {
         use crate::errors::VaultError;
@@ -227,6 +396,176 @@ impl VaultState {
     }
 }
 
+/// An empty vault holding `(protocol, amount)` allocations, for unit tests
+#[cfg(test)]
+pub fn test_vault(allocations: &[(u8, u64)]) -> VaultState {
+    let data = [0u8; VaultState::LEN];
+    let mut vault = VaultState::try_deserialize_unchecked(&mut &data[..]).unwrap();
+    vault.config = VaultConfig::default();
+    vault.num_allocations = allocations.len() as u8;
+    for (alloc, (protocol, amount)) in vault.allocations.iter_mut().zip(allocations) {
+        alloc.protocol = *protocol;
+        alloc.amount = *amount;
+    }
+    vault
+}
+
+/// Allocation layout written before the exchange rate guard
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct LegacyAllocation {
+    /// The LST protocol
+    pub protocol: u8,
+    /// Target allocation in basis points (0-10000)
+    pub target_bps: u16,
+    /// Current actual allocation in basis points
+    pub current_bps: u16,
+    /// Amount of SOL value in this protocol
+    pub amount: u64,
+}
+
+impl LegacyAllocation {
+    /// Size of a legacy Allocation in bytes
+    pub const LEN: usize = 1 + 2 + 2 + 8; // 13 bytes
+}
+
+/// Vault configuration layout written before the NAV, oracle and payout settings
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct LegacyVaultConfig {
+    /// Management fee in basis points
+    pub management_fee_bps: u16,
+    /// Performance fee in basis points
+    pub performance_fee_bps: u16,
+    /// Rebalance threshold in basis points
+    pub rebalance_threshold_bps: u16,
+    /// Maximum slippage allowed in basis points
+    pub max_slippage_bps: u16,
+    /// Deposit cap in lamports (0 = no cap)
+    pub deposit_cap: u64,
+    /// Whether the vault is paused
+    pub is_paused: bool,
+}
+
+impl LegacyVaultConfig {
+    /// Size of a legacy VaultConfig in bytes
+    pub const LEN: usize = 2 + 2 + 2 + 2 + 8 + 1; // 17 bytes
+}
+
+/// Vault state layout written by the first deployment
+/// 
+/// Only read by `migrate_vault`, which rewrites it into `VaultState`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyVaultState {
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+    /// Authority that can perform admin operations
+    pub authority: Pubkey,
+    /// jSOLi token mint address
+    pub jsoli_mint: Pubkey,
+    /// Total value locked in the vault (in lamports)
+    pub total_tvl: u64,
+    /// Total jSOL shares outstanding
+    pub total_shares: u64,
+    /// High water mark for performance fee calculation (in lamports per share)
+    pub high_water_mark: u64,
+    /// Accumulated management fees (in lamports)
+    pub accrued_management_fees: u64,
+    /// Accumulated performance fees (in lamports)
+    pub accrued_performance_fees: u64,
+    /// Last fee collection timestamp
+    pub last_fee_collection: i64,
+    /// Last rebalance timestamp
+    pub last_rebalance: i64,
+    /// Number of rebalances performed
+    pub rebalance_count: u64,
+    /// Number of unique depositors
+    pub depositor_count: u64,
+    /// Vault configuration
+    pub config: LegacyVaultConfig,
+    /// Number of active allocations
+    pub num_allocations: u8,
+    /// Protocol allocations (fixed size array)
+    pub allocations: [LegacyAllocation; MAX_PROTOCOLS],
+    /// Reserved space
+    pub reserved: [u8; 128],
+}
+
+impl LegacyVaultState {
+    /// Size of a legacy VaultState account in bytes
+    pub const LEN: usize = 8 + // Anchor discriminator
+        1 +                    // bump
+        32 +                   // authority
+        32 +                   // jsoli_mint
+        8 +                    // total_tvl
+        8 +                    // total_shares
+        8 +                    // high_water_mark
+        8 +                    // accrued_management_fees
+        8 +                    // accrued_performance_fees
+        8 +                    // last_fee_collection
+        8 +                    // last_rebalance
+        8 +                    // rebalance_count
+        8 +                    // depositor_count
+        LegacyVaultConfig::LEN + // config
+        1 +                    // num_allocations
+        (LegacyAllocation::LEN * MAX_PROTOCOLS) + // allocations
+        128;                   // reserved
+    
+    /// Decode a vault account still in the legacy layout
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        use anchor_lang::Discriminator;
+        use crate::errors::VaultError;
+        require!(
+            data.len() == Self::LEN && data[..8] == VaultState::DISCRIMINATOR,
+            VaultError::InvalidVaultState
+        );
+        Self::deserialize(&mut &data[8..]).map_err(|_| error!(VaultError::InvalidVaultState))
+    }
+    
+    /// Carry the legacy fields into the current layout
+    /// 
+    /// Settings added since take their defaults. The rate guard starts
+    /// without a baseline and the NAV as never refreshed, so the vault
+    /// needs a NAV refresh before deposits and withdrawals resume.
+    pub fn migrate(&self) -> VaultState {
+        let mut allocations = [Allocation::default(); MAX_PROTOCOLS];
+        for (alloc, legacy) in allocations.iter_mut().zip(self.allocations.iter()) {
+            alloc.protocol = legacy.protocol;
+            alloc.target_bps = legacy.target_bps;
+            alloc.current_bps = legacy.current_bps;
+            alloc.amount = legacy.amount;
+        }
+        
+        VaultState {
+            bump: self.bump,
+            authority: self.authority,
+            jsoli_mint: self.jsoli_mint,
+            total_tvl: self.total_tvl,
+            total_shares: self.total_shares,
+            high_water_mark: self.high_water_mark,
+            accrued_management_fees: self.accrued_management_fees,
+            accrued_performance_fees: self.accrued_performance_fees,
+            last_fee_collection: self.last_fee_collection,
+            last_rebalance: self.last_rebalance,
+            rebalance_count: self.rebalance_count,
+            depositor_count: self.depositor_count,
+            config: VaultConfig {
+                management_fee_bps: self.config.management_fee_bps,
+                performance_fee_bps: self.config.performance_fee_bps,
+                rebalance_threshold_bps: self.config.rebalance_threshold_bps,
+                max_slippage_bps: self.config.max_slippage_bps,
+                deposit_cap: self.config.deposit_cap,
+                is_paused: self.config.is_paused,
+                ..VaultConfig::default()
+            },
+            num_allocations: self.num_allocations,
+            allocations,
+            last_nav_slot: 0,
+            pending_unstake_lamports: 0,
+            queued_withdraw_lamports: 0,
+            reserved: [0; 120],
+        }
+    }
+}
+
 /// User account tracking individual positions
 #[account]
 pub struct UserAccount {
@@ -254,8 +593,11 @@ pub struct UserAccount {
     /// Number of pending withdrawal requests
     pub pending_withdrawals: u8,
     
+    /// Index of the next withdrawal request, never reused
+    pub next_request_index: u64,
+    
     /// Reserved space for future upgrades
-    pub reserved: [u8; 64],
+    pub reserved: [u8; 56],
 }
 
 impl UserAccount {
@@ -269,7 +611,37 @@ impl UserAccount {
         8 +                    // first_deposit
         8 +                    // last_activity
         1 +                    // pending_withdrawals
-        64;                    // reserved
+        8 +                    // next_request_index
+        56;                    // reserved
+    
+    /// Move `shares` from the position into a new withdrawal request
+    pub fn queue_withdrawal(&mut self, shares: u64, now: i64) -> Result<()> {
+        use crate::errors::VaultError;
+        self.shares = self.shares
+            .checked_sub(shares)
+            .ok_or(VaultError::InsufficientShares)?;
+        self.pending_withdrawals = self.pending_withdrawals
+            .checked_add(1)
+            .ok_or(VaultError::MathOverflow)?;
+        self.next_request_index = self.next_request_index
+            .checked_add(1)
+            .ok_or(VaultError::MathOverflow)?;
+        self.last_activity = now;
+        Ok(())
+    }
+    
+    /// Return the shares of a cancelled withdrawal request to the position
+    pub fn release_withdrawal(&mut self, shares: u64, now: i64) -> Result<()> {
+        use crate::errors::VaultError;
+        self.shares = self.shares
+            .checked_add(shares)
+            .ok_or(VaultError::MathOverflow)?;
+        self.pending_withdrawals = self.pending_withdrawals
+            .checked_sub(1)
+            .ok_or(VaultError::MathUnderflow)?;
+        self.last_activity = now;
+        Ok(())
+    }
 }
 
 /// Withdrawal request account
@@ -287,24 +659,113 @@ pub struct WithdrawRequest {
     /// Estimated lamports value at time of request
     pub estimated_lamports: u64,
     
+    /// Share price at time of request
+    pub request_share_price: u64,
+    
+    /// Settlement policy in force when the request was made
+    pub settlement_policy: SettlementPolicy,
+    
     /// Timestamp when the request was created
     pub created_at: i64,
     
-    /// Timestamp when the request becomes ready
+    /// Estimated timestamp when the request becomes ready, for display
     pub ready_at: i64,
     
     /// Current status of the request
     pub status: WithdrawStatus,
     
-    /// Request index for this user (for PDA derivation)
+    /// Request index for this user, from `next_request_index` (for PDA derivation)
     pub request_index: u64,
     
+    /// Protocol whose LST settles the request, or `None` for SOL
+    pub payout_protocol: Option<u8>,
+    
+    /// Epoch the request was created in
+    pub request_epoch: u64,
+    
+    /// Epoch from which the request can be completed
+    pub ready_epoch: u64,
+    
     /// Reserved space for future upgrades
     pub reserved: [u8; 32],
 }
 
 impl WithdrawRequest {
     /// Size of WithdrawRequest in bytes
+    pub const LEN: usize = 8 + // Anchor discriminator
+        1 +                    // bump
+        32 +                   // owner
+        8 +                    // shares
+        8 +                    // estimated_lamports
+        8 +                    // request_share_price
+        1 +                    // settlement_policy
+        8 +                    // created_at
+        8 +                    // ready_at
+        1 +                    // status
+        8 +                    // request_index
+        2 +                    // payout_protocol
+        8 +                    // request_epoch
+        8 +                    // ready_epoch
+        32;                    // reserved
+    
+    /// Shares returned to the owner if the request is cancelled
+    /// 
+    /// Requests settling at completion price get all their shares back.
+    /// Under the other policies the request fixed a claim on
+    /// `estimated_lamports`, so a cancel settles at the lower of that and
+    /// `completion_lamports`, the shares' current value: if the price rose,
+    /// only the shares worth `estimated_lamports` now are returned.
+    pub fn cancel_shares(&self, completion_lamports: u64) -> Result<u64> {
+        use crate::errors::VaultError;
+        if self.settlement_policy == SettlementPolicy::CompletionPrice
+            || completion_lamports <= self.estimated_lamports
+        {
+            return Ok(self.shares);
+        }
+        let result = (self.shares as u128)
+            .checked_mul(self.estimated_lamports as u128)
+            .ok_or(VaultError::MathOverflow)?
+            .checked_div(completion_lamports as u128)
+            .ok_or(VaultError::DivisionByZero)?;
+        Ok(result as u64)
+    }
+}
+
+/// Withdrawal request layout written before requests escrowed their jSOLi
+/// 
+/// Only read to close request accounts left open by that layout.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyWithdrawRequest {
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+    
+    /// Owner of this withdrawal request
+    pub owner: Pubkey,
+    
+    /// Number of shares being withdrawn
+    pub shares: u64,
+    
+    /// Estimated lamports value at time of request
+    pub estimated_lamports: u64,
+    
+    /// Timestamp when request was created
+    pub created_at: i64,
+    
+    /// Timestamp when withdrawal can be completed
+    pub ready_at: i64,
+    
+    /// Status of the withdrawal request
+    pub status: WithdrawStatus,
+    
+    /// Request index for this user
+    pub request_index: u64,
+    
+    /// Reserved space
+    pub reserved: [u8; 32],
+}
+
+impl LegacyWithdrawRequest {
+    /// Size of a legacy WithdrawRequest account in bytes
     pub const LEN: usize = 8 + // Anchor discriminator
         1 +                    // bump
         32 +                   // owner
@@ -317,6 +778,229 @@ impl WithdrawRequest {
         32;                    // reserved
 }
 
+/// Protocol registry entry for a supported LST
+///
+/// One PDA per protocol ID. Every stake, unstake and valuation path reads
+/// the protocol's integration details from here, so new LSTs join the index
+/// through a registry entry rather than a program upgrade.
+#[account]
+pub struct ProtocolConfig {
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+    
+    /// Protocol ID (matches `Allocation.protocol`)
+    pub protocol: u8,
+    
+    /// Adapter used to integrate the protocol
+    pub adapter: AdapterKind,
+    
+    /// Protocol program (stake pool program or Marinade program)
+    pub program_id: Pubkey,
+    
+    /// LST mint
+    pub mint: Pubkey,
+    
+    /// Stake pool account (SPL) or state account (Marinade)
+    pub pool: Pubkey,
+    
+    /// Vault-owned custody token account holding the LST
+    /// (PDA with seeds [CUSTODY_SEED, mint])
+    pub vault_token_account: Pubkey,
+    
+    /// Delayed unstaking period in seconds
+    pub unstake_delay: i64,
+    
+    /// Whether the protocol supports instant (liquid) unstaking
+    pub supports_instant_unstake: bool,
+    
+    /// Instant unstake fee in basis points
+    pub instant_unstake_fee_bps: u16,
+    
+    /// Whether staking into the protocol is enabled
+    pub is_active: bool,
+    
+    /// Haircut applied to the value of LST deposited directly, in basis points
+    pub lst_deposit_haircut_bps: u16,
+    
+    /// Reserved space for future upgrades
+    pub reserved: [u8; 64],
+}
+
+impl ProtocolConfig {
+    /// Size of ProtocolConfig in bytes
+    pub const LEN: usize = 8 + // Anchor discriminator
+        1 +                    // bump
+        1 +                    // protocol
+        1 +                    // adapter
+        32 +                   // program_id
+        32 +                   // mint
+        32 +                   // pool
+        32 +                   // vault_token_account
+        8 +                    // unstake_delay
+        1 +                    // supports_instant_unstake
+        2 +                    // instant_unstake_fee_bps
+        1 +                    // is_active
+        2 +                    // lst_deposit_haircut_bps
+        64;                    // reserved
+    
+    /// Build the adapter for this protocol
+    pub fn load_adapter(&self) -> Box<dyn crate::utils::LstAdapter> {
+        match self.adapter {
+            AdapterKind::SplStakePool => Box::new(crate::utils::SplStakePoolAdapter::new(
+                self.program_id,
+                self.pool,
+                self.mint,
+            )),
+            AdapterKind::Marinade => Box::new(crate::utils::MarinadeAdapter::new(
+                self.program_id,
+                self.pool,
+                self.mint,
+            )),
+        }
+    }
+}
+
+/// Open delayed-unstake tickets for one protocol
+///
+/// One PDA per protocol ID. Tickets are added when the vault orders a
+/// delayed unstake and removed when `claim_unstake` pays them out.
+#[account]
+pub struct PendingUnstakes {
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+    
+    /// Protocol the tickets belong to
+    pub protocol: u8,
+    
+    /// Index used to derive the next ticket PDA
+    pub next_ticket_index: u64,
+    
+    /// Number of open tickets
+    pub num_tickets: u8,
+    
+    /// Open tickets (fixed size array)
+    pub tickets: [PendingTicket; MAX_PENDING_TICKETS],
+    
+    /// Reserved space for future upgrades
+    pub reserved: [u8; 64],
+}
+
+impl PendingUnstakes {
+    /// Size of PendingUnstakes in bytes
+    pub const LEN: usize = 8 + // Anchor discriminator
+        1 +                    // bump
+        1 +                    // protocol
+        8 +                    // next_ticket_index
+        1 +                    // num_tickets
+        (PendingTicket::LEN * MAX_PENDING_TICKETS) + // tickets
+        64;                    // reserved
+    
+    /// Record a new ticket
+    pub fn push(&mut self, ticket: PendingTicket) -> Result<()> {
+        use crate::errors::VaultError;
+        let count = self.num_tickets as usize;
+        require!(count < MAX_PENDING_TICKETS, VaultError::PendingUnstakesFull);
+        self.tickets[count] = ticket;
+        self.num_tickets += 1;
+        Ok(())
+    }
+    
+    /// Remove a ticket by address, returning its entry
+    pub fn remove(&mut self, ticket: &Pubkey) -> Result<PendingTicket> {
+        use crate::errors::VaultError;
+        let count = self.num_tickets as usize;
+        let index = self.tickets[..count]
+            .iter()
+            .position(|entry| entry.ticket == *ticket)
+            .ok_or(VaultError::UnstakeTicketNotFound)?;
+        let entry = self.tickets[index];
+        self.tickets[index] = self.tickets[count - 1];
+        self.tickets[count - 1] = PendingTicket::default();
+        self.num_tickets -= 1;
+        Ok(entry)
+    }
+}
+
+/// A single price source of an oracle feed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OracleSource {
+    /// Provider that reads this source
+    pub provider: OracleProviderKind,
+    
+    /// Expected price account, or the default pubkey to accept any account
+    /// the provider validates (Pyth updates are matched by feed ID)
+    pub account: Pubkey,
+}
+
+impl OracleSource {
+    /// Size of OracleSource in bytes
+    pub const LEN: usize = 1 + // provider
+        32;                    // account
+    
+    /// Build the provider for this source
+    pub fn load_provider(&self) -> Box<dyn crate::utils::OracleProvider> {
+        match self.provider {
+            OracleProviderKind::Pyth => Box::new(crate::utils::PythProvider),
+            OracleProviderKind::Switchboard => Box::new(crate::utils::SwitchboardProvider),
+            OracleProviderKind::Mock => Box::new(crate::utils::MockProvider),
+        }
+    }
+}
+
+/// Oracle feed configuration
+/// 
+/// One PDA per Pyth feed ID, holding the price sources for that asset pair
+/// and the checks applied to their prices.
+#[account]
+pub struct OracleFeedConfig {
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+    
+    /// Pyth feed ID
+    pub feed_id: [u8; 32],
+    
+    /// Maximum age of an accepted price update in seconds
+    pub max_staleness_secs: i64,
+    
+    /// Whether updates must be fully verified (partially verified updates
+    /// are rejected)
+    pub require_full_verification: bool,
+    
+    /// How source prices are combined
+    pub aggregation: OracleAggregation,
+    
+    /// Minimum number of valid source prices for a median
+    pub min_sources: u8,
+    
+    /// Number of configured sources
+    pub num_sources: u8,
+    
+    /// Price sources, primary first
+    pub sources: [OracleSource; MAX_ORACLE_SOURCES],
+    
+    /// Reserved space for future upgrades
+    pub reserved: [u8; 64],
+}
+
+impl OracleFeedConfig {
+    /// Size of OracleFeedConfig in bytes
+    pub const LEN: usize = 8 + // Anchor discriminator
+        1 +                    // bump
+        32 +                   // feed_id
+        8 +                    // max_staleness_secs
+        1 +                    // require_full_verification
+        1 +                    // aggregation
+        1 +                    // min_sources
+        1 +                    // num_sources
+        OracleSource::LEN * MAX_ORACLE_SOURCES + // sources
+        64;                    // reserved
+    
+    /// Configured price sources, primary first
+    pub fn sources(&self) -> &[OracleSource] {
+        &self.sources[..(self.num_sources as usize).min(MAX_ORACLE_SOURCES)]
+    }
+}
+
 // ============================================================================
 // Events
 // ============================================================================
@@ -339,12 +1023,58 @@ pub struct DepositEvent {
     pub timestamp: i64,
 }
 
+/// Event emitted when a stake account is deposited
+#[event]
+pub struct StakeDepositEvent {
+    pub user: Pubkey,
+    pub stake_account: Pubkey,
+    pub voter: Pubkey,
+    pub protocol: u8,
+    pub active_stake: u64,
+    pub lamports: u64,
+    pub shares_minted: u64,
+    pub share_price: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when LST is deposited directly
+#[event]
+pub struct LstDepositEvent {
+    pub user: Pubkey,
+    pub protocol: u8,
+    pub mint: Pubkey,
+    pub lst_amount: u64,
+    pub exchange_rate: u64,
+    pub gross_value: u64,
+    pub credited_value: u64,
+    pub haircut_bps: u16,
+    pub shares_minted: u64,
+    pub share_price: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when jSOLi is redeemed for a slice of the vault's assets
+#[event]
+pub struct RedeemInKindEvent {
+    pub user: Pubkey,
+    pub shares: u64,
+    pub share_price: u64,
+    pub sol_lamports: u64,
+    pub assets: Vec<RedeemedAsset>,
+    pub total_value: u64,
+    pub timestamp: i64,
+}
+
 /// Event emitted when a withdrawal is requested
 #[event]
 pub struct WithdrawRequestEvent {
     pub user: Pubkey,
     pub shares: u64,
     pub estimated_lamports: u64,
+    pub payout_protocol: Option<u8>,
+    pub share_price: u64,
+    pub settlement_policy: SettlementPolicy,
+    pub ready_epoch: u64,
     pub ready_at: i64,
     pub timestamp: i64,
 }
@@ -354,10 +1084,111 @@ pub struct WithdrawRequestEvent {
 pub struct WithdrawCompleteEvent {
     pub user: Pubkey,
     pub shares: u64,
+    pub settlement_policy: SettlementPolicy,
+    pub settlement_price: u64,
     pub lamports_received: u64,
     pub timestamp: i64,
 }
 
+/// Event emitted when a withdrawal request is cancelled
+#[event]
+pub struct WithdrawCancelEvent {
+    pub user: Pubkey,
+    pub shares: u64,
+    pub shares_burned: u64,
+    pub request_index: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when shares are redeemed for a stake account
+#[event]
+pub struct StakeWithdrawEvent {
+    pub user: Pubkey,
+    pub stake_account: Pubkey,
+    pub protocol: u8,
+    pub shares: u64,
+    pub lamports: u64,
+    pub lst_amount: u64,
+    pub stake_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a withdrawal is settled in a single LST
+#[event]
+pub struct LstPayoutEvent {
+    pub user: Pubkey,
+    pub protocol: u8,
+    pub mint: Pubkey,
+    pub shares: u64,
+    pub gross_lamports: u64,
+    pub fee_lamports: u64,
+    pub exchange_rate: u64,
+    pub lst_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the vault NAV is refreshed
+#[event]
+pub struct NavUpdateEvent {
+    pub old_tvl: u64,
+    pub new_tvl: u64,
+    pub idle_lamports: u64,
+    pub pending_unstake_lamports: u64,
+    pub allocation_values: [u64; MAX_PROTOCOLS],
+    pub share_price: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the vault opens a delayed-unstake ticket
+#[event]
+pub struct UnstakeOrderedEvent {
+    pub protocol: u8,
+    pub ticket: Pubkey,
+    pub lst_amount: u64,
+    pub lamports: u64,
+    pub epoch: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a delayed-unstake ticket is claimed
+#[event]
+pub struct UnstakeClaimedEvent {
+    pub protocol: u8,
+    pub ticket: Pubkey,
+    pub lamports: u64,
+    pub received: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the vault instantly unstakes from a protocol
+#[event]
+pub struct InstantUnstakeEvent {
+    pub protocol: u8,
+    pub lamports: u64,
+    pub received: u64,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a protocol is quarantined by the rate guard
+#[event]
+pub struct RateQuarantinedEvent {
+    pub protocol: u8,
+    pub last_rate: u64,
+    pub new_rate: u64,
+    pub last_rate_epoch: u64,
+    pub epoch: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the authority clears a quarantine
+#[event]
+pub struct QuarantineClearedEvent {
+    pub protocol: u8,
+    pub timestamp: i64,
+}
+
 /// Event emitted when the vault is rebalanced
 #[event]
 pub struct RebalanceEvent {
@@ -382,6 +1213,52 @@ pub struct VaultPausedEvent {
     pub timestamp: i64,
 }
 
+/// Event emitted when a protocol is added to the registry
+#[event]
+pub struct ProtocolRegisteredEvent {
+    pub protocol: u8,
+    pub adapter: AdapterKind,
+    pub program_id: Pubkey,
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+    pub vault_token_account: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a protocol registry entry is updated
+#[event]
+pub struct ProtocolUpdatedEvent {
+    pub protocol: u8,
+    pub unstake_delay: i64,
+    pub supports_instant_unstake: bool,
+    pub instant_unstake_fee_bps: u16,
+    pub is_active: bool,
+    pub lst_deposit_haircut_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Event emitted when an oracle feed configuration is set
+#[event]
+pub struct OracleFeedUpdatedEvent {
+    pub feed_id: [u8; 32],
+    pub max_staleness_secs: i64,
+    pub require_full_verification: bool,
+    pub aggregation: OracleAggregation,
+    pub min_sources: u8,
+    pub sources: Vec<OracleSource>,
+    pub timestamp: i64,
+}
+
+/// Event emitted when an oracle price is refused for a wide confidence interval
+#[event]
+pub struct OracleConfidenceRejectedEvent {
+    pub price: u64,
+    pub confidence: u64,
+    pub confidence_bps: u64,
+    pub max_confidence_bps: u16,
+    pub timestamp: i64,
+}
+
 /// Event emitted when vault config is updated
 #[event]
 pub struct ConfigUpdatedEvent {
@@ -390,3 +1267,120 @@ pub struct ConfigUpdatedEvent {
     pub new_value: u64,
     pub timestamp: i64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+    
+    /// A vault account as written by the first deployment, field by field
+    fn baseline_vault_data(authority: Pubkey, jsoli_mint: Pubkey) -> Vec<u8> {
+        let mut data = VaultState::DISCRIMINATOR.to_vec();
+        data.push(254); // bump
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(jsoli_mint.as_ref());
+        for value in [
+            500_000_000_000u64, // total_tvl
+            480_000_000_000,    // total_shares
+            1_040_000_000,      // high_water_mark
+            7_000,              // accrued_management_fees
+            9_000,              // accrued_performance_fees
+            1_700_000_000,      // last_fee_collection
+            1_700_000_100,      // last_rebalance
+            12,                 // rebalance_count
+            34,                 // depositor_count
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        
+        // config: fees, threshold, slippage, deposit cap, is_paused
+        for bps in [50u16, 1000, 500, 100] {
+            data.extend_from_slice(&bps.to_le_bytes());
+        }
+        data.extend_from_slice(&1_000_000_000_000u64.to_le_bytes());
+        data.push(1);
+        
+        // num_allocations, then protocol, target_bps, current_bps, amount
+        data.push(2);
+        for slot in 0..MAX_PROTOCOLS {
+            let (protocol, target_bps, current_bps, amount) = match slot {
+                0 => (0u8, 6000u16, 5900u16, 295_000_000_000u64),
+                1 => (3, 4000, 4100, 205_000_000_000),
+                _ => (0, 0, 0, 0),
+            };
+            data.push(protocol);
+            data.extend_from_slice(&target_bps.to_le_bytes());
+            data.extend_from_slice(&current_bps.to_le_bytes());
+            data.extend_from_slice(&amount.to_le_bytes());
+        }
+        
+        data.extend_from_slice(&[0u8; 128]); // reserved
+        data
+    }
+    
+    #[test]
+    fn test_decode_baseline_vault() {
+        let authority = Pubkey::new_unique();
+        let jsoli_mint = Pubkey::new_unique();
+        let data = baseline_vault_data(authority, jsoli_mint);
+        assert_eq!(data.len(), LegacyVaultState::LEN);
+        
+        // The baseline layout no longer decodes as the current one
+        assert!(VaultState::try_deserialize(&mut &data[..]).is_err());
+        
+        let vault = LegacyVaultState::try_from_account_data(&data).unwrap().migrate();
+        assert_eq!(vault.bump, 254);
+        assert_eq!(vault.authority, authority);
+        assert_eq!(vault.jsoli_mint, jsoli_mint);
+        assert_eq!(vault.total_tvl, 500_000_000_000);
+        assert_eq!(vault.total_shares, 480_000_000_000);
+        assert_eq!(vault.high_water_mark, 1_040_000_000);
+        assert_eq!(vault.accrued_management_fees, 7_000);
+        assert_eq!(vault.accrued_performance_fees, 9_000);
+        assert_eq!(vault.last_fee_collection, 1_700_000_000);
+        assert_eq!(vault.last_rebalance, 1_700_000_100);
+        assert_eq!(vault.rebalance_count, 12);
+        assert_eq!(vault.depositor_count, 34);
+        
+        assert_eq!(vault.config.management_fee_bps, 50);
+        assert_eq!(vault.config.performance_fee_bps, 1000);
+        assert_eq!(vault.config.rebalance_threshold_bps, 500);
+        assert_eq!(vault.config.max_slippage_bps, 100);
+        assert_eq!(vault.config.deposit_cap, 1_000_000_000_000);
+        assert!(vault.config.is_paused);
+        assert_eq!(
+            vault.config.max_nav_staleness_slots,
+            VaultConfig::default().max_nav_staleness_slots
+        );
+        
+        assert_eq!(vault.num_allocations, 2);
+        assert_eq!(vault.allocations[1].protocol, 3);
+        assert_eq!(vault.allocations[1].target_bps, 4000);
+        assert_eq!(vault.allocations[1].current_bps, 4100);
+        assert_eq!(vault.allocations[1].amount, 205_000_000_000);
+        assert_eq!(vault.allocations[1].last_rate, 0);
+        assert!(!vault.allocations[1].is_quarantined);
+        assert_eq!(vault.last_nav_slot, 0);
+        
+        // The migrated vault round-trips through the current layout
+        let mut migrated = Vec::new();
+        vault.try_serialize(&mut migrated).unwrap();
+        assert_eq!(migrated.len(), VaultState::LEN);
+        let decoded = VaultState::try_deserialize(&mut &migrated[..]).unwrap();
+        assert_eq!(decoded.total_tvl, vault.total_tvl);
+        assert_eq!(decoded.allocations[0].amount, 295_000_000_000);
+    }
+    
+    #[test]
+    fn test_decode_baseline_vault_rejects_other_accounts() {
+        let data = baseline_vault_data(Pubkey::new_unique(), Pubkey::new_unique());
+        
+        // Already migrated, or truncated
+        assert!(LegacyVaultState::try_from_account_data(&data[..data.len() - 1]).is_err());
+        
+        // Another account type of the same size
+        let mut other = data.clone();
+        other[..8].copy_from_slice(&UserAccount::DISCRIMINATOR);
+        assert!(LegacyVaultState::try_from_account_data(&other).is_err());
+    }
+}
@@ -0,0 +1,371 @@
+//! LST adapter interface
+//!
+//! Every supported protocol implements `LstAdapter`. Staking, unstaking,
+//! rebalancing and valuation all dispatch through it, with the
+//! protocol-specific accounts taken from `remaining_accounts`.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+use crate::constants::*;
+use crate::errors::VaultError;
+use crate::state::ProtocolConfig;
+use crate::utils::math::calculate_bps;
+use crate::utils::protocols::get_instant_unstake_fee;
+
+/// Operation performed through an adapter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdapterOperation {
+    /// Stake SOL for LST
+    Deposit,
+    /// Deposit a delegated stake account for LST
+    DepositStake,
+    /// Redeem LST for a stake account split from the protocol's stake
+    WithdrawStake,
+    /// Swap LST for SOL immediately, paying the protocol's fee
+    LiquidUnstake,
+    /// Start a delayed (epoch-based) unstake
+    DelayedUnstake,
+    /// Claim a matured delayed unstake
+    Claim,
+    /// Read the LST exchange rate
+    ExchangeRate,
+}
+
+/// Vault-owned accounts shared by every adapter operation
+pub struct VaultAccounts<'a, 'info> {
+    /// Vault state PDA, authority of the vault token accounts
+    pub vault: &'a AccountInfo<'info>,
+    /// Vault SOL PDA, source and destination of lamports
+    pub vault_sol: &'a AccountInfo<'info>,
+    /// Registered vault token account for the protocol's LST
+    pub vault_token_account: &'a AccountInfo<'info>,
+    /// System program
+    pub system_program: &'a AccountInfo<'info>,
+    /// Token program
+    pub token_program: &'a AccountInfo<'info>,
+    /// Vault state PDA bump
+    pub vault_bump: u8,
+    /// Vault SOL PDA bump
+    pub vault_sol_bump: u8,
+}
+
+/// Common interface for LST protocol integrations
+///
+/// Protocol accounts are passed in the order documented by each
+/// implementation. The first account is always the protocol's state
+/// account, so any operation's account list can also be used to read the
+/// exchange rate.
+pub trait LstAdapter {
+    /// Number of protocol accounts `operation` reads from `remaining_accounts`
+    fn required_accounts(&self, operation: AdapterOperation) -> usize;
+
+    /// Whether the protocol supports delayed unstaking
+    fn supports_delayed_unstake(&self) -> bool;
+
+    /// Space and owner program of a delayed-unstake ticket account
+    ///
+    /// The vault allocates the ticket with this layout before calling
+    /// `delayed_unstake`. Returns `None` without delayed unstaking.
+    fn ticket_layout(&self) -> Option<(usize, Pubkey)>;
+
+    /// Read the lamports a ticket pays out on claim
+    fn ticket_lamports(&self, vault: &VaultAccounts, ticket_account: &AccountInfo) -> Result<u64>;
+
+    /// Stake `lamports` from the vault SOL PDA, minting LST to the vault
+    /// token account
+    fn deposit<'info>(
+        &self,
+        vault: &VaultAccounts<'_, 'info>,
+        accounts: &[AccountInfo<'info>],
+        lamports: u64,
+    ) -> Result<()>;
+
+    /// Deposit `stake_account`, whose stake and withdraw authority is the
+    /// vault PDA, minting LST to the vault token account
+    fn deposit_stake<'info>(
+        &self,
+        vault: &VaultAccounts<'_, 'info>,
+        stake_account: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+    ) -> Result<()>;
+
+    /// Redeem `lst_amount` for stake split into `stake_account`, an
+    /// uninitialized stake account whose staker and withdrawer become
+    /// `stake_authority`
+    fn withdraw_stake<'info>(
+        &self,
+        vault: &VaultAccounts<'_, 'info>,
+        stake_account: &AccountInfo<'info>,
+        stake_authority: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+        lst_amount: u64,
+    ) -> Result<()>;
+
+    /// Redeem `lst_amount` immediately for SOL paid to the vault SOL PDA
+    fn liquid_unstake<'info>(
+        &self,
+        vault: &VaultAccounts<'_, 'info>,
+        accounts: &[AccountInfo<'info>],
+        lst_amount: u64,
+    ) -> Result<()>;
+
+    /// Start a delayed unstake of `lst_amount` into `ticket_account`,
+    /// allocated per `ticket_layout`
+    fn delayed_unstake<'info>(
+        &self,
+        vault: &VaultAccounts<'_, 'info>,
+        ticket_account: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+        lst_amount: u64,
+    ) -> Result<()>;
+
+    /// Claim the matured `ticket_account` into the vault SOL PDA
+    fn claim<'info>(
+        &self,
+        vault: &VaultAccounts<'_, 'info>,
+        ticket_account: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+    ) -> Result<()>;
+
+    /// Read the LST exchange rate (lamports per LST token)
+    ///
+    /// Returns exchange rate scaled by SHARE_PRECISION
+    fn exchange_rate(&self, accounts: &[AccountInfo]) -> Result<u64>;
+}
+
+/// Check that enough protocol accounts were passed for `operation`
+pub fn check_accounts<'a, 'info>(
+    adapter: &dyn LstAdapter,
+    operation: AdapterOperation,
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<&'a [AccountInfo<'info>]> {
+    let required = adapter.required_accounts(operation);
+    require!(required > 0, VaultError::UnsupportedProtocol);
+    require!(accounts.len() >= required, VaultError::MissingProtocolAccounts);
+    Ok(&accounts[..required])
+}
+
+/// Stake lamports through an adapter
+///
+/// Returns the SOL value of the LST received, measured from the vault token
+/// account balance and valued at the post-deposit exchange rate.
+pub fn adapter_deposit<'info>(
+    adapter: &dyn LstAdapter,
+    vault: &VaultAccounts<'_, 'info>,
+    accounts: &[AccountInfo<'info>],
+    lamports: u64,
+) -> Result<u64> {
+    require!(
+        vault.vault_sol.lamports() >= lamports,
+        VaultError::InsufficientLiquidity
+    );
+
+    let balance_before = accessor::amount(vault.vault_token_account)?;
+    adapter.deposit(vault, accounts, lamports)?;
+    let minted = accessor::amount(vault.vault_token_account)?
+        .checked_sub(balance_before)
+        .ok_or(VaultError::MathUnderflow)?;
+    require!(minted > 0, VaultError::ProtocolIntegrationFailed);
+
+    let rate = adapter.exchange_rate(accounts)?;
+    let value = lst_to_lamports(minted, rate)?;
+
+    msg!("Received {} LST worth {} lamports", minted, value);
+
+    Ok(value)
+}
+
+/// Deposit a vault-controlled stake account through an adapter
+///
+/// Returns the SOL value of the LST received, measured from the vault token
+/// account balance and valued at the post-deposit exchange rate.
+pub fn adapter_deposit_stake<'info>(
+    adapter: &dyn LstAdapter,
+    vault: &VaultAccounts<'_, 'info>,
+    stake_account: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+) -> Result<u64> {
+    let balance_before = accessor::amount(vault.vault_token_account)?;
+    adapter.deposit_stake(vault, stake_account, accounts)?;
+    let minted = accessor::amount(vault.vault_token_account)?
+        .checked_sub(balance_before)
+        .ok_or(VaultError::MathUnderflow)?;
+    require!(minted > 0, VaultError::ProtocolIntegrationFailed);
+
+    let rate = adapter.exchange_rate(accounts)?;
+    let value = lst_to_lamports(minted, rate)?;
+
+    msg!("Received {} LST worth {} lamports for stake", minted, value);
+
+    Ok(value)
+}
+
+/// Withdraw a stake account worth `lamports` through an adapter
+///
+/// Redeems the LST equivalent of `lamports` at the current exchange rate.
+/// Returns the LST amount redeemed and the lamports in the new stake
+/// account, which are below `lamports` by any pool withdrawal fee.
+pub fn adapter_withdraw_stake<'info>(
+    adapter: &dyn LstAdapter,
+    vault: &VaultAccounts<'_, 'info>,
+    stake_account: &AccountInfo<'info>,
+    stake_authority: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+    lamports: u64,
+) -> Result<(u64, u64)> {
+    let rate = adapter.exchange_rate(accounts)?;
+    let lst_amount = lamports_to_lst(lamports, rate)?;
+    require!(lst_amount > 0, VaultError::ZeroAmount);
+    require!(
+        accessor::amount(vault.vault_token_account)? >= lst_amount,
+        VaultError::InsufficientLiquidity
+    );
+
+    let lamports_before = stake_account.lamports();
+    adapter.withdraw_stake(vault, stake_account, stake_authority, accounts, lst_amount)?;
+    let received = stake_account.lamports()
+        .checked_sub(lamports_before)
+        .ok_or(VaultError::MathUnderflow)?;
+
+    msg!("Withdrew {} LST as {} lamports of stake", lst_amount, received);
+
+    Ok((lst_amount, received))
+}
+
+/// Unstake the LST equivalent of `lamports` through an adapter
+///
+/// Opens a delayed unstake into `ticket_account` when one is passed,
+/// otherwise liquid-unstakes. Returns the lamports received by the vault SOL
+/// PDA (zero for delayed unstakes, which pay out on claim).
+pub fn adapter_unstake<'info>(
+    adapter: &dyn LstAdapter,
+    vault: &VaultAccounts<'_, 'info>,
+    accounts: &[AccountInfo<'info>],
+    lamports: u64,
+    ticket_account: Option<&AccountInfo<'info>>,
+) -> Result<u64> {
+    let rate = adapter.exchange_rate(accounts)?;
+    let lst_amount = lamports_to_lst(lamports, rate)?;
+    require!(lst_amount > 0, VaultError::ZeroAmount);
+    require!(
+        accessor::amount(vault.vault_token_account)? >= lst_amount,
+        VaultError::InsufficientLiquidity
+    );
+
+    let lamports_before = vault.vault_sol.lamports();
+    if let Some(ticket_account) = ticket_account {
+        adapter.delayed_unstake(vault, ticket_account, accounts, lst_amount)?;
+    } else {
+        adapter.liquid_unstake(vault, accounts, lst_amount)?;
+    }
+    let received = vault.vault_sol.lamports()
+        .checked_sub(lamports_before)
+        .ok_or(VaultError::MathUnderflow)?;
+
+    msg!("Unstaked {} LST, received {} lamports", lst_amount, received);
+
+    Ok(received)
+}
+
+/// Instantly unstake the LST equivalent of `lamports` through an adapter
+///
+/// Liquid-unstakes into the vault SOL PDA and measures the fee the pool
+/// actually charged. The fee may exceed the registry's
+/// `instant_unstake_fee_bps` by at most `max_slippage_bps`. Returns the
+/// lamports received and the fee charged.
+pub fn adapter_instant_unstake<'info>(
+    adapter: &dyn LstAdapter,
+    config: &ProtocolConfig,
+    vault: &VaultAccounts<'_, 'info>,
+    accounts: &[AccountInfo<'info>],
+    lamports: u64,
+    max_slippage_bps: u16,
+) -> Result<(u64, u64)> {
+    let expected_fee_bps = get_instant_unstake_fee(config).ok_or(VaultError::UnsupportedProtocol)?;
+
+    let received = adapter_unstake(adapter, vault, accounts, lamports, None)?;
+    let fee = check_instant_unstake_fee(lamports, received, expected_fee_bps, max_slippage_bps)?;
+
+    Ok((received, fee))
+}
+
+/// Check the fee an instant unstake of `lamports` charged
+///
+/// The fee is what `received` falls short of `lamports`, and may be at most
+/// `expected_fee_bps` plus `max_slippage_bps` of it. Returns the fee.
+pub fn check_instant_unstake_fee(
+    lamports: u64,
+    received: u64,
+    expected_fee_bps: u16,
+    max_slippage_bps: u16,
+) -> Result<u64> {
+    let fee = lamports.saturating_sub(received);
+    let max_fee = calculate_bps(lamports, expected_fee_bps.saturating_add(max_slippage_bps))?;
+    require!(fee <= max_fee, VaultError::SlippageExceeded);
+
+    msg!("Instant unstake fee {} lamports (max {})", fee, max_fee);
+
+    Ok(fee)
+}
+
+/// Value the vault's custody balance for a protocol
+///
+/// Returns the custody token account balance times the adapter's current
+/// exchange rate, in lamports.
+pub fn custody_value(
+    adapter: &dyn LstAdapter,
+    vault_token_account: &AccountInfo,
+    accounts: &[AccountInfo],
+) -> Result<u64> {
+    let balance = accessor::amount(vault_token_account)?;
+    let rate = adapter.exchange_rate(accounts)?;
+    lst_to_lamports(balance, rate)
+}
+
+/// Convert an LST amount into lamports at the given exchange rate
+pub fn lst_to_lamports(lst_amount: u64, rate: u64) -> Result<u64> {
+    let lamports = (lst_amount as u128)
+        .checked_mul(rate as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(SHARE_PRECISION as u128)
+        .ok_or(VaultError::DivisionByZero)?;
+
+    Ok(lamports as u64)
+}
+
+/// Convert lamports into an LST amount at the given exchange rate
+pub fn lamports_to_lst(lamports: u64, rate: u64) -> Result<u64> {
+    let lst_amount = (lamports as u128)
+        .checked_mul(SHARE_PRECISION as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(rate as u128)
+        .ok_or(VaultError::DivisionByZero)?;
+
+    Ok(lst_amount as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instant_unstake_fee_bound() {
+        // 0.3% expected plus 0.5% slippage allows up to 0.8% of 1 SOL
+        assert_eq!(check_instant_unstake_fee(1_000_000_000, 997_000_000, 30, 50).unwrap(), 3_000_000);
+        assert_eq!(check_instant_unstake_fee(1_000_000_000, 992_000_000, 30, 50).unwrap(), 8_000_000);
+        assert_eq!(
+            check_instant_unstake_fee(1_000_000_000, 991_999_999, 30, 50).unwrap_err(),
+            VaultError::SlippageExceeded.into()
+        );
+
+        // Receiving more than the LST's value charges no fee
+        assert_eq!(check_instant_unstake_fee(1_000_000_000, 1_000_000_100, 30, 0).unwrap(), 0);
+
+        // Without slippage the registry fee is the hard limit
+        assert_eq!(
+            check_instant_unstake_fee(1_000_000_000, 996_999_999, 30, 0).unwrap_err(),
+            VaultError::SlippageExceeded.into()
+        );
+    }
+}
@@ -4,50 +4,371 @@
 
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
     program::invoke_signed,
-    system_instruction,
 };
 use crate::errors::VaultError;
 
-/// Stake SOL to Jito (SPL Stake Pool)
-pub fn stake_jito_sol<'info>(
-    _pool: &AccountInfo<'info>,
-    _withdraw_authority: &AccountInfo<'info>,
-    _reserve_stake: &AccountInfo<'info>,
-    _mint: &AccountInfo<'info>,
-    _user_token_account: &AccountInfo<'info>,
-    _vault_sol_account: &AccountInfo<'info>,
-    _stake_pool_program: &AccountInfo<'info>,
-    _token_program: &AccountInfo<'info>,
+/// Stake SOL to Marinade
+///
+/// Calls Marinade `deposit` with the vault SOL PDA as `transfer_from`.
+/// The minted mSOL lands in `user_token_account`.
+#[allow(clippy::too_many_arguments)]
+pub fn stake_marinade<'info>(
+    marinade_state: &AccountInfo<'info>,
+    msol_mint: &AccountInfo<'info>,
+    liq_pool_sol_pda: &AccountInfo<'info>,
+    liq_pool_msol_leg: &AccountInfo<'info>,
+    liq_pool_msol_leg_authority: &AccountInfo<'info>,
+    reserve_pda: &AccountInfo<'info>,
+    msol_mint_authority: &AccountInfo<'info>,
+    user_token_account: &AccountInfo<'info>,
+    vault_sol_account: &AccountInfo<'info>,
+    marinade_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    vault_sol_seeds: &[&[&[u8]]],
     lamports: u64,
 ) -> Result<()> {
-    // In a production implementation, we would use spl_stake_pool::instruction::deposit_sol
-    // but we can also use raw invoke if needed.
-    
-    msg!("CPI: Staking {} lamports to Jito", lamports);
-    
-    // Placeholder for actual CPI
-    // Real implementation would use spl_stake_pool::instruction::deposit_sol
-    
+    msg!("CPI: Staking {} lamports to Marinade", lamports);
+
+    let mut data = marinade::DEPOSIT_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    let ix = Instruction {
+        program_id: *marinade_program.key,
+        accounts: vec![
+            AccountMeta::new(*marinade_state.key, false),
+            AccountMeta::new(*msol_mint.key, false),
+            AccountMeta::new(*liq_pool_sol_pda.key, false),
+            AccountMeta::new(*liq_pool_msol_leg.key, false),
+            AccountMeta::new_readonly(*liq_pool_msol_leg_authority.key, false),
+            AccountMeta::new(*reserve_pda.key, false),
+            AccountMeta::new(*vault_sol_account.key, true),
+            AccountMeta::new(*user_token_account.key, false),
+            AccountMeta::new_readonly(*msol_mint_authority.key, false),
+            AccountMeta::new_readonly(*system_program.key, false),
+            AccountMeta::new_readonly(*token_program.key, false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &ix,
+        &[
+            marinade_state.clone(),
+            msol_mint.clone(),
+            liq_pool_sol_pda.clone(),
+            liq_pool_msol_leg.clone(),
+            liq_pool_msol_leg_authority.clone(),
+            reserve_pda.clone(),
+            vault_sol_account.clone(),
+            user_token_account.clone(),
+            msol_mint_authority.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            marinade_program.clone(),
+        ],
+        vault_sol_seeds,
+    )
+    .map_err(|_| VaultError::ProtocolIntegrationFailed)?;
+
     Ok(())
 }
 
-/// Stake SOL to Marinade
-pub fn stake_marinade<'info>(
-    _marinade_state: &AccountInfo<'info>,
-    _msol_mint: &AccountInfo<'info>,
-    _liq_pool_sol_pda: &AccountInfo<'info>,
-    _liqp_pool_msol_pda: &AccountInfo<'info>,
-    _user_token_account: &AccountInfo<'info>,
-    _vault_sol_account: &AccountInfo<'info>,
-    _marinade_program: &AccountInfo<'info>,
-    _token_program: &AccountInfo<'info>,
-    lamports: u64,
+/// Liquid-unstake mSOL through Marinade's liquidity pool
+///
+/// Calls Marinade `liquid_unstake`. `msol_authority` owns `msol_from` and
+/// signs through `signer_seeds`. SOL, less the pool fee, is sent to
+/// `sol_to`, which must be a system account.
+#[allow(clippy::too_many_arguments)]
+pub fn liquid_unstake_marinade<'info>(
+    marinade_state: &AccountInfo<'info>,
+    msol_mint: &AccountInfo<'info>,
+    liq_pool_sol_pda: &AccountInfo<'info>,
+    liq_pool_msol_leg: &AccountInfo<'info>,
+    treasury_msol_account: &AccountInfo<'info>,
+    msol_from: &AccountInfo<'info>,
+    msol_authority: &AccountInfo<'info>,
+    sol_to: &AccountInfo<'info>,
+    marinade_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    msol_amount: u64,
 ) -> Result<()> {
-    msg!("CPI: Staking {} lamports to Marinade", lamports);
-    
-    // Placeholder for actual CPI 
-    // Real implementation would use marinade_sdk::instruction::liquid_stake
-    
+    msg!("CPI: Liquid unstaking {} mSOL from Marinade", msol_amount);
+
+    let mut data = marinade::LIQUID_UNSTAKE_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&msol_amount.to_le_bytes());
+
+    let ix = Instruction {
+        program_id: *marinade_program.key,
+        accounts: vec![
+            AccountMeta::new(*marinade_state.key, false),
+            AccountMeta::new(*msol_mint.key, false),
+            AccountMeta::new(*liq_pool_sol_pda.key, false),
+            AccountMeta::new(*liq_pool_msol_leg.key, false),
+            AccountMeta::new(*treasury_msol_account.key, false),
+            AccountMeta::new(*msol_from.key, false),
+            AccountMeta::new_readonly(*msol_authority.key, true),
+            AccountMeta::new(*sol_to.key, false),
+            AccountMeta::new_readonly(*system_program.key, false),
+            AccountMeta::new_readonly(*token_program.key, false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &ix,
+        &[
+            marinade_state.clone(),
+            msol_mint.clone(),
+            liq_pool_sol_pda.clone(),
+            liq_pool_msol_leg.clone(),
+            treasury_msol_account.clone(),
+            msol_from.clone(),
+            msol_authority.clone(),
+            sol_to.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            marinade_program.clone(),
+        ],
+        signer_seeds,
+    )
+    .map_err(|_| VaultError::ProtocolIntegrationFailed)?;
+
     Ok(())
 }
+
+/// Open a Marinade delayed-unstake ticket
+///
+/// Calls Marinade `order_unstake`, burning mSOL from `msol_from`. The ticket
+/// beneficiary is `msol_authority`, which signs through `signer_seeds` and
+/// must be a system account to receive the claim. `ticket_account` must
+/// already be allocated, zeroed and owned by Marinade.
+#[allow(clippy::too_many_arguments)]
+pub fn order_unstake_marinade<'info>(
+    marinade_state: &AccountInfo<'info>,
+    msol_mint: &AccountInfo<'info>,
+    msol_from: &AccountInfo<'info>,
+    msol_authority: &AccountInfo<'info>,
+    ticket_account: &AccountInfo<'info>,
+    clock: &AccountInfo<'info>,
+    rent: &AccountInfo<'info>,
+    marinade_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    msol_amount: u64,
+) -> Result<()> {
+    msg!("CPI: Ordering unstake of {} mSOL from Marinade", msol_amount);
+
+    let mut data = marinade::ORDER_UNSTAKE_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&msol_amount.to_le_bytes());
+
+    let ix = Instruction {
+        program_id: *marinade_program.key,
+        accounts: vec![
+            AccountMeta::new(*marinade_state.key, false),
+            AccountMeta::new(*msol_mint.key, false),
+            AccountMeta::new(*msol_from.key, false),
+            AccountMeta::new_readonly(*msol_authority.key, true),
+            AccountMeta::new(*ticket_account.key, false),
+            AccountMeta::new_readonly(*clock.key, false),
+            AccountMeta::new_readonly(*rent.key, false),
+            AccountMeta::new_readonly(*token_program.key, false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &ix,
+        &[
+            marinade_state.clone(),
+            msol_mint.clone(),
+            msol_from.clone(),
+            msol_authority.clone(),
+            ticket_account.clone(),
+            clock.clone(),
+            rent.clone(),
+            token_program.clone(),
+            marinade_program.clone(),
+        ],
+        signer_seeds,
+    )
+    .map_err(|_| VaultError::ProtocolIntegrationFailed)?;
+
+    Ok(())
+}
+
+/// Claim a matured Marinade unstake ticket
+///
+/// Calls Marinade `claim`. `sol_to` must be the ticket beneficiary.
+pub fn claim_marinade<'info>(
+    marinade_state: &AccountInfo<'info>,
+    reserve_pda: &AccountInfo<'info>,
+    ticket_account: &AccountInfo<'info>,
+    sol_to: &AccountInfo<'info>,
+    clock: &AccountInfo<'info>,
+    marinade_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    msg!("CPI: Claiming Marinade ticket {}", ticket_account.key);
+
+    let ix = Instruction {
+        program_id: *marinade_program.key,
+        accounts: vec![
+            AccountMeta::new(*marinade_state.key, false),
+            AccountMeta::new(*reserve_pda.key, false),
+            AccountMeta::new(*ticket_account.key, false),
+            AccountMeta::new(*sol_to.key, false),
+            AccountMeta::new_readonly(*clock.key, false),
+            AccountMeta::new_readonly(*system_program.key, false),
+        ],
+        data: marinade::CLAIM_DISCRIMINATOR.to_vec(),
+    };
+
+    invoke_signed(
+        &ix,
+        &[
+            marinade_state.clone(),
+            reserve_pda.clone(),
+            ticket_account.clone(),
+            sol_to.clone(),
+            clock.clone(),
+            system_program.clone(),
+            marinade_program.clone(),
+        ],
+        &[],
+    )
+    .map_err(|_| VaultError::ProtocolIntegrationFailed)?;
+
+    Ok(())
+}
+
+/// Marinade Finance program layout and helpers
+pub mod marinade {
+    use super::*;
+
+    /// Anchor discriminator for `deposit`
+    pub const DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+
+    /// Anchor discriminator for `liquid_unstake`
+    pub const LIQUID_UNSTAKE_DISCRIMINATOR: [u8; 8] = [30, 30, 119, 240, 191, 227, 12, 16];
+
+    /// Anchor discriminator for `order_unstake`
+    pub const ORDER_UNSTAKE_DISCRIMINATOR: [u8; 8] = [97, 167, 144, 107, 117, 190, 128, 36];
+
+    /// Anchor discriminator for `claim`
+    pub const CLAIM_DISCRIMINATOR: [u8; 8] = [62, 198, 214, 193, 213, 159, 108, 210];
+
+    /// Anchor discriminator for the `State` account
+    pub const STATE_DISCRIMINATOR: [u8; 8] = [216, 146, 107, 94, 104, 75, 182, 177];
+
+    /// Anchor discriminator for the `TicketAccountData` account
+    pub const TICKET_DISCRIMINATOR: [u8; 8] = [133, 77, 18, 98, 211, 1, 231, 3];
+
+    /// Size of a ticket account: discriminator, state, beneficiary,
+    /// lamports_amount and created_epoch
+    pub const TICKET_ACCOUNT_LEN: usize = 8 + 32 + 32 + 8 + 8;
+
+    /// Byte offset of `TicketAccountData.beneficiary`
+    pub const TICKET_BENEFICIARY_OFFSET: usize = 40;
+
+    /// Byte offset of `TicketAccountData.lamports_amount`
+    pub const TICKET_LAMPORTS_OFFSET: usize = 72;
+
+    /// Byte offset of `State.msol_mint`
+    pub const STATE_MSOL_MINT_OFFSET: usize = 8;
+
+    /// Byte offset of `State.msol_price`
+    pub const STATE_MSOL_PRICE_OFFSET: usize = 512;
+
+    /// Denominator of `State.msol_price` (a 32.32 fixed-point value)
+    pub const MSOL_PRICE_DENOMINATOR: u64 = 0x1_0000_0000;
+
+    /// Seed for the liquidity pool SOL leg PDA
+    pub const LIQ_POOL_SOL_LEG_SEED: &[u8] = b"liq_sol";
+
+    /// Seed for the liquidity pool mSOL leg authority PDA
+    pub const LIQ_POOL_MSOL_LEG_AUTHORITY_SEED: &[u8] = b"liq_st_sol_authority";
+
+    /// Seed for the reserve PDA
+    pub const RESERVE_SEED: &[u8] = b"reserve";
+
+    /// Seed for the mSOL mint authority PDA
+    pub const MSOL_MINT_AUTHORITY_SEED: &[u8] = b"st_mint";
+
+    /// Derive a Marinade PDA from the state account and a seed
+    pub fn find_pda(marinade_program: &Pubkey, state: &Pubkey, seed: &[u8]) -> Pubkey {
+        Pubkey::find_program_address(&[state.as_ref(), seed], marinade_program).0
+    }
+
+    /// Read the mSOL price (lamports per mSOL, 32.32 fixed-point) from the
+    /// Marinade state account
+    ///
+    /// Verifies the account owner, discriminator and mSOL mint first.
+    pub fn read_msol_price(
+        state: &AccountInfo,
+        marinade_program: &Pubkey,
+        msol_mint: &Pubkey,
+    ) -> Result<u64> {
+        require_keys_eq!(*state.owner, *marinade_program, VaultError::InvalidAccountOwner);
+
+        let data = state.try_borrow_data()?;
+        require!(
+            data.len() >= STATE_MSOL_PRICE_OFFSET + 8,
+            VaultError::ProtocolIntegrationFailed
+        );
+        require!(
+            data[..8] == STATE_DISCRIMINATOR,
+            VaultError::ProtocolIntegrationFailed
+        );
+
+        let mint = Pubkey::try_from(&data[STATE_MSOL_MINT_OFFSET..STATE_MSOL_MINT_OFFSET + 32])
+            .map_err(|_| VaultError::ProtocolIntegrationFailed)?;
+        require_keys_eq!(mint, *msol_mint, VaultError::InvalidMint);
+
+        let mut price = [0u8; 8];
+        price.copy_from_slice(&data[STATE_MSOL_PRICE_OFFSET..STATE_MSOL_PRICE_OFFSET + 8]);
+
+        Ok(u64::from_le_bytes(price))
+    }
+
+    /// Read the lamports owed by a delayed-unstake ticket
+    ///
+    /// Verifies the account owner, discriminator and beneficiary first.
+    pub fn read_ticket_lamports(
+        ticket: &AccountInfo,
+        marinade_program: &Pubkey,
+        beneficiary: &Pubkey,
+    ) -> Result<u64> {
+        require_keys_eq!(*ticket.owner, *marinade_program, VaultError::InvalidAccountOwner);
+
+        let data = ticket.try_borrow_data()?;
+        require!(
+            data.len() >= TICKET_ACCOUNT_LEN && data[..8] == TICKET_DISCRIMINATOR,
+            VaultError::ProtocolIntegrationFailed
+        );
+
+        let owner = Pubkey::try_from(&data[TICKET_BENEFICIARY_OFFSET..TICKET_BENEFICIARY_OFFSET + 32])
+            .map_err(|_| VaultError::ProtocolIntegrationFailed)?;
+        require_keys_eq!(owner, *beneficiary, VaultError::InvalidProtocolAccount);
+
+        let mut lamports = [0u8; 8];
+        lamports.copy_from_slice(&data[TICKET_LAMPORTS_OFFSET..TICKET_LAMPORTS_OFFSET + 8]);
+
+        Ok(u64::from_le_bytes(lamports))
+    }
+
+    /// Convert an mSOL amount into lamports at the given mSOL price
+    pub fn msol_to_lamports(msol: u64, msol_price: u64) -> Result<u64> {
+        let lamports = (msol as u128)
+            .checked_mul(msol_price as u128)
+            .ok_or(VaultError::MathOverflow)?
+            .checked_div(MSOL_PRICE_DENOMINATOR as u128)
+            .ok_or(VaultError::DivisionByZero)?;
+
+        Ok(lamports as u64)
+    }
+}
@@ -0,0 +1,260 @@
+//! Marinade Finance adapter
+//!
+//! Stakes SOL for mSOL and unstakes through either Marinade's liquidity pool
+//! (instant) or delayed-unstake tickets claimed after the epoch boundary.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Approve};
+
+use crate::constants::*;
+use crate::errors::VaultError;
+use crate::utils::adapter::{check_accounts, AdapterOperation, LstAdapter, VaultAccounts};
+use crate::utils::cpi::{
+    claim_marinade, liquid_unstake_marinade, marinade, order_unstake_marinade, stake_marinade,
+};
+
+/// Adapter for a Marinade deployment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarinadeAdapter {
+    /// Marinade program
+    pub program_id: Pubkey,
+    /// Marinade state account
+    pub state: Pubkey,
+    /// mSOL mint
+    pub mint: Pubkey,
+}
+
+impl MarinadeAdapter {
+    /// Create an adapter for the given Marinade state
+    pub fn new(program_id: Pubkey, state: Pubkey, mint: Pubkey) -> Self {
+        Self { program_id, state, mint }
+    }
+
+    /// Derive a PDA from the Marinade state account
+    pub fn pda(&self, seed: &[u8]) -> Pubkey {
+        marinade::find_pda(&self.program_id, &self.state, seed)
+    }
+
+    /// Validate the state account and program passed for an operation
+    fn validate_state(&self, state: &AccountInfo, program: &AccountInfo) -> Result<()> {
+        require_keys_eq!(program.key(), self.program_id, VaultError::InvalidProtocolAccount);
+        require_keys_eq!(state.key(), self.state, VaultError::InvalidProtocolAccount);
+        Ok(())
+    }
+}
+
+/// Protocol accounts, in `remaining_accounts` order.
+///
+/// Deposit:
+/// 0. Marinade state (writable)
+/// 1. mSOL mint (writable)
+/// 2. Liquidity pool SOL leg PDA (writable)
+/// 3. Liquidity pool mSOL leg token account (writable)
+/// 4. Liquidity pool mSOL leg authority PDA
+/// 5. Reserve PDA (writable)
+/// 6. mSOL mint authority PDA
+/// 7. Marinade program
+///
+/// Liquid unstake:
+/// 0. Marinade state (writable)
+/// 1. mSOL mint (writable)
+/// 2. Liquidity pool SOL leg PDA (writable)
+/// 3. Liquidity pool mSOL leg token account (writable)
+/// 4. Treasury mSOL token account (writable)
+/// 5. Marinade program
+///
+/// Delayed unstake:
+/// 0. Marinade state (writable)
+/// 1. mSOL mint (writable)
+/// 2. Clock sysvar
+/// 3. Rent sysvar
+/// 4. Marinade program
+///
+/// Claim:
+/// 0. Marinade state (writable)
+/// 1. Reserve PDA (writable)
+/// 2. Clock sysvar
+/// 3. Marinade program
+///
+/// Ticket accounts are passed separately. Their beneficiary is the vault
+/// SOL PDA, so claims pay straight into idle SOL.
+///
+/// Stake account deposits and withdrawals are not supported.
+impl LstAdapter for MarinadeAdapter {
+    fn required_accounts(&self, operation: AdapterOperation) -> usize {
+        match operation {
+            AdapterOperation::Deposit => 8,
+            AdapterOperation::DepositStake | AdapterOperation::WithdrawStake => 0,
+            AdapterOperation::LiquidUnstake => 6,
+            AdapterOperation::DelayedUnstake => 5,
+            AdapterOperation::Claim => 4,
+            AdapterOperation::ExchangeRate => 1,
+        }
+    }
+
+    fn supports_delayed_unstake(&self) -> bool {
+        true
+    }
+
+    fn ticket_layout(&self) -> Option<(usize, Pubkey)> {
+        Some((marinade::TICKET_ACCOUNT_LEN, self.program_id))
+    }
+
+    fn ticket_lamports(&self, vault: &VaultAccounts, ticket_account: &AccountInfo) -> Result<u64> {
+        marinade::read_ticket_lamports(ticket_account, &self.program_id, vault.vault_sol.key)
+    }
+
+    fn deposit<'info>(
+        &self,
+        vault: &VaultAccounts<'_, 'info>,
+        accounts: &[AccountInfo<'info>],
+        lamports: u64,
+    ) -> Result<()> {
+        let accounts = check_accounts(self, AdapterOperation::Deposit, accounts)?;
+        self.validate_state(&accounts[0], &accounts[7])?;
+        require_keys_eq!(accounts[1].key(), self.mint, VaultError::InvalidMint);
+        require_keys_eq!(accounts[2].key(), self.pda(marinade::LIQ_POOL_SOL_LEG_SEED), VaultError::InvalidProtocolAccount);
+        require_keys_eq!(accounts[4].key(), self.pda(marinade::LIQ_POOL_MSOL_LEG_AUTHORITY_SEED), VaultError::InvalidProtocolAccount);
+        require_keys_eq!(accounts[5].key(), self.pda(marinade::RESERVE_SEED), VaultError::InvalidProtocolAccount);
+        require_keys_eq!(accounts[6].key(), self.pda(marinade::MSOL_MINT_AUTHORITY_SEED), VaultError::InvalidProtocolAccount);
+
+        let vault_sol_seeds: &[&[u8]] = &[VAULT_SOL_SEED, &[vault.vault_sol_bump]];
+        stake_marinade(
+            &accounts[0],
+            &accounts[1],
+            &accounts[2],
+            &accounts[3],
+            &accounts[4],
+            &accounts[5],
+            &accounts[6],
+            vault.vault_token_account,
+            vault.vault_sol,
+            &accounts[7],
+            vault.system_program,
+            vault.token_program,
+            &[vault_sol_seeds],
+            lamports,
+        )
+    }
+
+    fn deposit_stake<'info>(
+        &self,
+        _vault: &VaultAccounts<'_, 'info>,
+        _stake_account: &AccountInfo<'info>,
+        _accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        err!(VaultError::UnsupportedProtocol)
+    }
+
+    fn withdraw_stake<'info>(
+        &self,
+        _vault: &VaultAccounts<'_, 'info>,
+        _stake_account: &AccountInfo<'info>,
+        _stake_authority: &AccountInfo<'info>,
+        _accounts: &[AccountInfo<'info>],
+        _lst_amount: u64,
+    ) -> Result<()> {
+        err!(VaultError::UnsupportedProtocol)
+    }
+
+    fn liquid_unstake<'info>(
+        &self,
+        vault: &VaultAccounts<'_, 'info>,
+        accounts: &[AccountInfo<'info>],
+        lst_amount: u64,
+    ) -> Result<()> {
+        let accounts = check_accounts(self, AdapterOperation::LiquidUnstake, accounts)?;
+        self.validate_state(&accounts[0], &accounts[5])?;
+        require_keys_eq!(accounts[1].key(), self.mint, VaultError::InvalidMint);
+        require_keys_eq!(accounts[2].key(), self.pda(marinade::LIQ_POOL_SOL_LEG_SEED), VaultError::InvalidProtocolAccount);
+
+        let vault_seeds: &[&[u8]] = &[VAULT_SEED, &[vault.vault_bump]];
+        liquid_unstake_marinade(
+            &accounts[0],
+            &accounts[1],
+            &accounts[2],
+            &accounts[3],
+            &accounts[4],
+            vault.vault_token_account,
+            vault.vault,
+            vault.vault_sol,
+            &accounts[5],
+            vault.system_program,
+            vault.token_program,
+            &[vault_seeds],
+            lst_amount,
+        )
+    }
+
+    fn delayed_unstake<'info>(
+        &self,
+        vault: &VaultAccounts<'_, 'info>,
+        ticket_account: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+        lst_amount: u64,
+    ) -> Result<()> {
+        let accounts = check_accounts(self, AdapterOperation::DelayedUnstake, accounts)?;
+        self.validate_state(&accounts[0], &accounts[4])?;
+        require_keys_eq!(accounts[1].key(), self.mint, VaultError::InvalidMint);
+
+        // The ticket beneficiary is the burn authority and must be a system
+        // account, so delegate the mSOL to the vault SOL PDA for the burn
+        let vault_seeds: &[&[u8]] = &[VAULT_SEED, &[vault.vault_bump]];
+        token::approve(
+            CpiContext::new_with_signer(
+                vault.token_program.clone(),
+                Approve {
+                    to: vault.vault_token_account.clone(),
+                    delegate: vault.vault_sol.clone(),
+                    authority: vault.vault.clone(),
+                },
+                &[vault_seeds],
+            ),
+            lst_amount,
+        )?;
+
+        let vault_sol_seeds: &[&[u8]] = &[VAULT_SOL_SEED, &[vault.vault_sol_bump]];
+        order_unstake_marinade(
+            &accounts[0],
+            &accounts[1],
+            vault.vault_token_account,
+            vault.vault_sol,
+            ticket_account,
+            &accounts[2],
+            &accounts[3],
+            &accounts[4],
+            vault.token_program,
+            &[vault_sol_seeds],
+            lst_amount,
+        )
+    }
+
+    fn claim<'info>(
+        &self,
+        vault: &VaultAccounts<'_, 'info>,
+        ticket_account: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let accounts = check_accounts(self, AdapterOperation::Claim, accounts)?;
+        self.validate_state(&accounts[0], &accounts[3])?;
+        require_keys_eq!(accounts[1].key(), self.pda(marinade::RESERVE_SEED), VaultError::InvalidProtocolAccount);
+
+        claim_marinade(
+            &accounts[0],
+            &accounts[1],
+            ticket_account,
+            vault.vault_sol,
+            &accounts[2],
+            &accounts[3],
+            vault.system_program,
+        )
+    }
+
+    fn exchange_rate(&self, accounts: &[AccountInfo]) -> Result<u64> {
+        let accounts = check_accounts(self, AdapterOperation::ExchangeRate, accounts)?;
+        require_keys_eq!(accounts[0].key(), self.state, VaultError::InvalidProtocolAccount);
+
+        let msol_price = marinade::read_msol_price(&accounts[0], &self.program_id, &self.mint)?;
+        marinade::msol_to_lamports(SHARE_PRECISION, msol_price)
+    }
+}
@@ -40,6 +40,22 @@ pub fn calculate_bps(value: u64, bps: u16) -> Result<u64> {
     Ok(result as u64)
 }
 
+/// Apply a haircut in basis points
+/// Returns value - (value * haircut_bps) / 10000, rounding in the vault's favour
+pub fn apply_haircut(value: u64, haircut_bps: u16) -> Result<u64> {
+    let remaining_bps = 10000u64
+        .checked_sub(haircut_bps as u64)
+        .ok_or(VaultError::MathUnderflow)?;
+    
+    let result = (value as u128)
+        .checked_mul(remaining_bps as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(10000)
+        .ok_or(VaultError::DivisionByZero)?;
+    
+    Ok(result as u64)
+}
+
 /// Calculate share price with precision
 /// Returns (total_value * precision) / total_shares
 pub fn calculate_share_price(total_value: u64, total_shares: u64, precision: u64) -> Result<u64> {
@@ -121,6 +137,21 @@ mod tests {
         assert_eq!(calculate_bps(1000, 50).unwrap(), 5);
     }
     
+    #[test]
+    fn test_apply_haircut() {
+        // No haircut credits the full value
+        assert_eq!(apply_haircut(1_000_000, 0).unwrap(), 1_000_000);
+        
+        // 0.5% haircut on 1000 = 995
+        assert_eq!(apply_haircut(1000, 50).unwrap(), 995);
+        
+        // Rounds down, never crediting more than the value
+        assert_eq!(apply_haircut(999, 1).unwrap(), 998);
+        
+        // Haircuts above 100% are rejected
+        assert!(apply_haircut(1000, 10001).is_err());
+    }
+    
     #[test]
     fn test_share_price_calculation() {
         let precision = 1_000_000_000u64;
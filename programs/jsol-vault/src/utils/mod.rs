@@ -3,11 +3,19 @@
 pub mod math;
 pub mod validation;
 pub mod oracle;
+pub mod switchboard;
 pub mod protocols;
 pub mod cpi;
+pub mod stake_pool;
+pub mod marinade;
+pub mod adapter;
 
 pub use math::*;
 pub use validation::*;
 pub use oracle::*;
+pub use switchboard::*;
 pub use protocols::*;
 pub use cpi::*;
+pub use stake_pool::*;
+pub use marinade::*;
+pub use adapter::*;
@@ -1,65 +1,309 @@
 //! Oracle integration utilities
 //!
-//! Handles price feeds and exchange rate lookups.
+//! Handles price feeds and exchange rate lookups. Prices are read through
+//! the `OracleProvider` trait (Pyth, Switchboard or a localnet mock) and
+//! combined per feed by `get_oracle_price`.
 
 use anchor_lang::prelude::*;
+use std::str::FromStr;
 use crate::constants::*;
 use crate::errors::VaultError;
-use crate::state::LstProtocol;
+use crate::state::{OracleAggregation, OracleConfidenceRejectedEvent, OracleFeedConfig, ProtocolConfig};
 
 /// Oracle price data
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OraclePrice {
-    pub price: u64,        // Price in lamports (scaled)
+    pub price: u64,        // Price mantissa
     pub confidence: u64,   // Confidence interval
+    pub exponent: i32,     // Price = mantissa * 10^exponent
     pub timestamp: i64,    // Unix timestamp of last update
 }
 
-/// Get protocol APY (placeholder - would integrate with actual oracle)
+impl OraclePrice {
+    /// Express the price and confidence with a different exponent
+    pub fn rescale(&self, exponent: i32) -> Result<OraclePrice> {
+        let rescale = |value: u64| -> Result<u64> {
+            let shift = (self.exponent - exponent).unsigned_abs();
+            let factor = 10u128
+                .checked_pow(shift)
+                .ok_or(VaultError::MathOverflow)?;
+            let scaled = if self.exponent >= exponent {
+                (value as u128).checked_mul(factor).ok_or(VaultError::MathOverflow)?
+            } else {
+                (value as u128) / factor
+            };
+            u64::try_from(scaled).map_err(|_| error!(VaultError::MathOverflow))
+        };
+        
+        Ok(OraclePrice {
+            price: rescale(self.price)?,
+            confidence: rescale(self.confidence)?,
+            exponent,
+            timestamp: self.timestamp,
+        })
+    }
+}
+
+/// A source of asset prices
+pub trait OracleProvider {
+    /// Read and validate the price held by `account`
+    /// 
+    /// Implementations check the account owner and layout, the feed's
+    /// staleness limit and that the price is positive.
+    fn get_price(
+        &self,
+        account: &AccountInfo,
+        feed: &OracleFeedConfig,
+        current_time: i64,
+    ) -> Result<OraclePrice>;
+}
+
+/// Pyth pull oracle provider
+pub struct PythProvider;
+
+impl OracleProvider for PythProvider {
+    fn get_price(
+        &self,
+        account: &AccountInfo,
+        feed: &OracleFeedConfig,
+        current_time: i64,
+    ) -> Result<OraclePrice> {
+        get_pyth_price(account, feed, current_time)
+    }
+}
+
+/// Localnet provider reading a fixed-layout price account
+/// 
+/// Layout (little endian): `price: i64, conf: u64, exponent: i32,
+/// publish_time: i64`. Only available with the `mock-oracle` feature.
+pub struct MockProvider;
+
+impl OracleProvider for MockProvider {
+    fn get_price(
+        &self,
+        account: &AccountInfo,
+        feed: &OracleFeedConfig,
+        current_time: i64,
+    ) -> Result<OraclePrice> {
+        require!(
+            cfg!(any(test, feature = "mock-oracle")),
+            VaultError::InvalidOracleConfig
+        );
+        
+        let data = account.try_borrow_data()?;
+        require!(data.len() >= 28, VaultError::InvalidOracleAccount);
+        let price = i64::from_le_bytes(data[0..8].try_into().unwrap());
+        let confidence = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let exponent = i32::from_le_bytes(data[16..20].try_into().unwrap());
+        let timestamp = i64::from_le_bytes(data[20..28].try_into().unwrap());
+        
+        validate_price_age(timestamp, current_time, feed.max_staleness_secs)?;
+        require!(price > 0, VaultError::InvalidOracleAccount);
+        
+        Ok(OraclePrice {
+            price: price as u64,
+            confidence,
+            exponent,
+            timestamp,
+        })
+    }
+}
+
+/// Read a feed's price from its configured sources
+/// 
+/// `accounts` holds one price account per configured source, in source
+/// order; trailing accounts may be omitted for sources that should be
+/// skipped. Prices are normalized to `ORACLE_PRICE_EXPONENT`.
+/// 
+/// With `Fallback` the first valid price wins. With `Median` the median of
+/// all valid prices is returned once at least `min_sources` sources
+/// return one. Returns `OracleUnavailable` only when every source fails.
 /// 
-/// Returns APY in basis points (e.g., 500 = 5%)
-pub fn get_protocol_apy(_protocol: LstProtocol) -> Result<u64> {
-    // In production, this would query:
-    // 1. Pyth Network for on-chain APY feeds
-    // 2. Protocol-specific state accounts
-    // 3. Historical performance calculations
-    
-    // Placeholder values based on typical LST APYs
-    Ok(match _protocol {
-        LstProtocol::Jito => 750,       // 7.5% (includes MEV rewards)
-        LstProtocol::Marinade => 670,   // 6.7%
-        LstProtocol::BlazeStake => 680, // 6.8%
-        LstProtocol::Jupiter => 700,    // 7.0%
+/// A price whose confidence interval is wider than `max_confidence_bps`
+/// counts as a failed source, so the next source is tried. Its
+/// `OracleConfidenceRejectedEvent` is then recorded with the successful
+/// transaction. If every valid price was rejected for confidence, the
+/// result is `OracleConfidenceTooWide` and the events only appear in the
+/// failed transaction's logs.
+pub fn get_oracle_price(
+    feed: &OracleFeedConfig,
+    accounts: &[AccountInfo],
+    max_confidence_bps: u16,
+    current_time: i64,
+) -> Result<OraclePrice> {
+    let mut prices: Vec<OraclePrice> = Vec::with_capacity(MAX_ORACLE_SOURCES);
+    let mut confidence_rejected = false;
+    
+    for (i, source) in feed.sources().iter().enumerate() {
+        let Some(account) = accounts.get(i) else {
+            msg!("Oracle source {} skipped: no account", i);
+            continue;
+        };
+        if source.account != Pubkey::default() && account.key() != source.account {
+            msg!("Oracle source {} skipped: unexpected account {}", i, account.key());
+            continue;
+        }
+        
+        let price = source
+            .load_provider()
+            .get_price(account, feed, current_time)
+            .and_then(|price| price.rescale(ORACLE_PRICE_EXPONENT))
+            .and_then(|price| validate_price_confidence(&price, max_confidence_bps).map(|_| price));
+        match price {
+            Ok(price) => {
+                if feed.aggregation == OracleAggregation::Fallback {
+                    return Ok(price);
+                }
+                prices.push(price);
+            }
+            Err(e) => {
+                confidence_rejected |= e == VaultError::OracleConfidenceTooWide.into();
+                msg!("Oracle source {} failed: {}", i, e);
+            }
+        }
+    }
+    
+    if prices.is_empty() && confidence_rejected {
+        return err!(VaultError::OracleConfidenceTooWide);
+    }
+    require!(!prices.is_empty(), VaultError::OracleUnavailable);
+    require!(
+        prices.len() >= feed.min_sources as usize,
+        VaultError::OracleQuorumNotMet
+    );
+    
+    prices.sort_by_key(|price| price.price);
+    let mid = prices.len() / 2;
+    if prices.len() % 2 == 1 {
+        return Ok(prices.swap_remove(mid));
+    }
+    
+    let (low, high) = (&prices[mid - 1], &prices[mid]);
+    Ok(OraclePrice {
+        price: ((low.price as u128 + high.price as u128) / 2) as u64,
+        confidence: low.confidence.max(high.confidence),
+        exponent: ORACLE_PRICE_EXPONENT,
+        timestamp: low.timestamp.min(high.timestamp),
     })
 }
 
-/// Get LST exchange rate (LST per SOL)
+/// Annualized protocol APY from two exchange rate observations
+/// 
+/// Returns APY in basis points (e.g., 500 = 5%), without compounding.
+/// A falling rate yields zero.
+pub fn get_protocol_apy(
+    previous_rate: u64,
+    previous_timestamp: i64,
+    current_rate: u64,
+    current_timestamp: i64,
+) -> Result<u64> {
+    let seconds_per_year: i64 = 31_536_000;
+    
+    let elapsed = current_timestamp
+        .checked_sub(previous_timestamp)
+        .ok_or(VaultError::MathUnderflow)?;
+    if previous_rate == 0 || elapsed <= 0 || current_rate <= previous_rate {
+        return Ok(0);
+    }
+    
+    let apy = ((current_rate - previous_rate) as u128)
+        .checked_mul(BPS_DENOMINATOR as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_mul(seconds_per_year as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(previous_rate as u128)
+        .ok_or(VaultError::DivisionByZero)?
+        .checked_div(elapsed as u128)
+        .ok_or(VaultError::DivisionByZero)?;
+    
+    Ok(apy as u64)
+}
+
+/// Get the live LST exchange rate (lamports per LST token)
+/// 
+/// Reads the SPL stake pool `total_lamports / pool_token_supply` or the
+/// Marinade `State` mSOL price from `state_account`, which must be the
+/// registered pool/state account and owned by the protocol program.
 /// 
 /// Returns exchange rate scaled by SHARE_PRECISION
-pub fn get_lst_exchange_rate(_protocol: LstProtocol) -> Result<u64> {
-    // In production, this would query the protocol's state account
-    // to get the current exchange rate
-    
-    // For now, return a slightly-above-1 rate (LSTs appreciate over time)
-    // This represents the accumulated staking rewards
-    Ok(match _protocol {
-        LstProtocol::Jito => 1_050_000_000,       // 1.05 SOL per jitoSOL
-        LstProtocol::Marinade => 1_045_000_000,   // 1.045 SOL per mSOL
-        LstProtocol::BlazeStake => 1_042_000_000, // 1.042 SOL per bSOL
-        LstProtocol::Jupiter => 1_048_000_000,    // 1.048 SOL per JupSOL
-    })
+pub fn get_lst_exchange_rate(config: &ProtocolConfig, state_account: &AccountInfo) -> Result<u64> {
+    config
+        .load_adapter()
+        .exchange_rate(std::slice::from_ref(state_account))
+}
+
+/// Read a feed's price for pricing vault assets
+/// 
+/// `primary` is the account of the feed's first source and `fallbacks` the
+/// accounts of the remaining sources, in configured order. The aggregated
+/// price is refused if its confidence interval exceeds
+/// `max_confidence_bps`.
+pub fn get_validated_price<'info>(
+    feed: &OracleFeedConfig,
+    primary: &AccountInfo<'info>,
+    fallbacks: &[AccountInfo<'info>],
+    max_confidence_bps: u16,
+    current_time: i64,
+) -> Result<OraclePrice> {
+    let accounts: Vec<AccountInfo<'info>> = std::iter::once(primary.clone())
+        .chain(fallbacks.iter().cloned())
+        .collect();
+    let price = get_oracle_price(feed, &accounts, max_confidence_bps, current_time)?;
+    
+    msg!("Oracle Price: {} (conf: {}, expo: {})", price.price, price.confidence, price.exponent);
+    
+    Ok(price)
 }
 
 /// Validate oracle data freshness
 pub fn validate_oracle_freshness(oracle_timestamp: i64, current_timestamp: i64) -> Result<()> {
-    let age = current_timestamp
-        .checked_sub(oracle_timestamp)
+    validate_price_age(oracle_timestamp, current_timestamp, MAX_ORACLE_STALENESS_SECS)
+}
+
+/// Validate that a price published at `publish_time` is at most
+/// `max_staleness_secs` old
+pub fn validate_price_age(publish_time: i64, current_time: i64, max_staleness_secs: i64) -> Result<()> {
+    let age = current_time
+        .checked_sub(publish_time)
         .ok_or(VaultError::MathUnderflow)?;
     
-    require!(
-        age <= MAX_ORACLE_STALENESS_SECS,
-        VaultError::StaleOraclePrice
-    );
+    require!(age <= max_staleness_secs, VaultError::StaleOraclePrice);
+    
+    Ok(())
+}
+
+/// Refuse a price whose confidence interval is too wide
+/// 
+/// The interval is measured as basis points of the price. A limit of 0
+/// disables the check. On rejection an `OracleConfidenceRejectedEvent` is
+/// emitted ahead of the error; `get_oracle_price` treats the error as a
+/// failed source and moves on to the next one.
+pub fn validate_price_confidence(price: &OraclePrice, max_confidence_bps: u16) -> Result<()> {
+    if max_confidence_bps == 0 {
+        return Ok(());
+    }
+    require!(price.price > 0, VaultError::InvalidOracleAccount);
+    
+    // Compare confidence / price > max_bps / BPS_DENOMINATOR without rounding
+    let scaled_confidence = (price.confidence as u128)
+        .checked_mul(BPS_DENOMINATOR as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    let limit = (price.price as u128)
+        .checked_mul(max_confidence_bps as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    if scaled_confidence > limit {
+        let confidence_bps = u64::try_from(scaled_confidence / price.price as u128).unwrap_or(u64::MAX);
+        emit!(OracleConfidenceRejectedEvent {
+            price: price.price,
+            confidence: price.confidence,
+            confidence_bps,
+            max_confidence_bps,
+            timestamp: price.timestamp,
+        });
+        msg!("Oracle confidence {} bps exceeds {} bps", confidence_bps, max_confidence_bps);
+        return err!(VaultError::OracleConfidenceTooWide);
+    }
     
     Ok(())
 }
@@ -98,16 +342,15 @@ pub fn validate_price_deviation(
 
 /// Calculate weighted average APY across allocations
 pub fn get_weighted_average_apy(
-    allocations: &[(LstProtocol, u16)], // (protocol, allocation_bps)
+    allocations: &[(u64, u16)], // (apy_bps, allocation_bps)
 ) -> Result<u64> {
     let mut weighted_sum: u128 = 0;
     let mut total_allocation: u128 = 0;
     
-    for (protocol, bps) in allocations {
-        let apy = get_protocol_apy(*protocol)?;
+    for (apy, bps) in allocations {
         weighted_sum = weighted_sum
             .checked_add(
-                (apy as u128)
+                (*apy as u128)
                     .checked_mul(*bps as u128)
                     .ok_or(VaultError::MathOverflow)?
             )
@@ -128,21 +371,39 @@ pub fn get_weighted_average_apy(
     Ok(weighted_apy as u64)
 }
 
-/// Get the price of an asset from Pyth
-pub fn get_pyth_price(oracle_account: &AccountInfo, max_age: i64) -> Result<OraclePrice> {
-    use pyth_sdk_solana::load_price_feed_from_account_info;
-
-    let price_feed = load_price_feed_from_account_info(oracle_account)
+/// Get the price of an asset from a Pyth `PriceUpdateV2` account
+/// 
+/// The account must be owned by the Pyth receiver program and carry the
+/// feed ID configured in `feed`. The update's verification level and age
+/// are checked against the feed configuration.
+pub fn get_pyth_price(
+    price_update: &AccountInfo,
+    feed: &OracleFeedConfig,
+    current_time: i64,
+) -> Result<OraclePrice> {
+    let receiver = Pubkey::from_str(PYTH_RECEIVER_PROGRAM_ID)
         .map_err(|_| VaultError::InvalidOracleAccount)?;
+    require_keys_eq!(*price_update.owner, receiver, VaultError::InvalidOracleAccount);
+    
+    let data = price_update.try_borrow_data()?;
+    let update = pyth_receiver::parse_price_update(&data)?;
+    
+    require!(update.feed_id == feed.feed_id, VaultError::InvalidOracleAccount);
+    if feed.require_full_verification {
+        require!(
+            update.verification_level == pyth_receiver::VerificationLevel::Full,
+            VaultError::OracleVerificationTooLow
+        );
+    }
+    
+    validate_price_age(update.publish_time, current_time, feed.max_staleness_secs)?;
+    require!(update.price > 0, VaultError::InvalidOracleAccount);
     
-    let current_time = Clock::get()?.unix_timestamp;
-    let price = price_feed.get_price_no_older_than(current_time, max_age.try_into().unwrap_or(0))
-        .ok_or(VaultError::StaleOraclePrice)?;
-
     Ok(OraclePrice {
-        price: price.price as u64,
-        confidence: price.conf,
-        timestamp: price.publish_time,
+        price: update.price as u64,
+        confidence: update.conf,
+        exponent: update.exponent,
+        timestamp: update.publish_time,
     })
 }
 
@@ -152,11 +413,359 @@ pub fn get_pda_bump(seeds: &[&[u8]], program_id: &Pubkey) -> u8 {
     bump
 }
 
-/// Pyth price feed IDs (mainnet)
+/// Pyth price feed IDs
 pub mod pyth_feeds {
-    /// SOL/USD price feed
-    pub const SOL_USD: &str = "H6ARHf6YXhGYeQfUzQNGk6rDNnLBQKrenN712K4AQJEG";
+    /// SOL/USD feed ID
+    pub const SOL_USD: [u8; 32] = [
+        0xef, 0x0d, 0x8b, 0x6f, 0xda, 0x2c, 0xeb, 0xa4, 0x1d, 0xa1, 0x5d, 0x40, 0x95, 0xd1, 0xda, 0x39,
+        0x2a, 0x0d, 0x2f, 0x8e, 0xd0, 0xc6, 0xc7, 0xbc, 0x0f, 0x4c, 0xfa, 0xc8, 0xc2, 0x80, 0xb5, 0x6d,
+    ];
     
-    /// BTC/USD price feed
-    pub const BTC_USD: &str = "GVXRSBjFk6e6J3NbVPXohDJetcTjaeeuykUpbQF8UoMU";
+    /// BTC/USD feed ID
+    pub const BTC_USD: [u8; 32] = [
+        0xe6, 0x2d, 0xf6, 0xc8, 0xb4, 0xa8, 0x5f, 0xe1, 0xa6, 0x7d, 0xb4, 0x4d, 0xc1, 0x2d, 0xe5, 0xdb,
+        0x33, 0x0f, 0x7a, 0xc6, 0x6b, 0x72, 0xdc, 0x65, 0x8a, 0xfe, 0xdf, 0x0f, 0x4a, 0x41, 0x5b, 0x43,
+    ];
+}
+
+/// Pyth Solana receiver `PriceUpdateV2` account layout
+pub mod pyth_receiver {
+    use super::*;
+    
+    /// Anchor discriminator for the `PriceUpdateV2` account
+    pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+    
+    /// Wormhole verification level of a price update
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum VerificationLevel {
+        /// Verified by a subset of guardians
+        Partial { num_signatures: u8 },
+        /// Verified by a full guardian quorum
+        Full,
+    }
+    
+    /// Decoded `PriceUpdateV2` account
+    #[derive(Debug, Clone)]
+    pub struct PriceUpdate {
+        pub write_authority: Pubkey,
+        pub verification_level: VerificationLevel,
+        pub feed_id: [u8; 32],
+        pub price: i64,
+        pub conf: u64,
+        pub exponent: i32,
+        pub publish_time: i64,
+        pub posted_slot: u64,
+    }
+    
+    /// Decode a `PriceUpdateV2` account
+    /// 
+    /// The verification level is a Borsh enum, so the price message offset
+    /// depends on its variant and fields are read sequentially.
+    pub fn parse_price_update(data: &[u8]) -> Result<PriceUpdate> {
+        require!(
+            data.len() >= 8 && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR,
+            VaultError::InvalidOracleAccount
+        );
+        let mut reader = Reader { data, offset: 8 };
+        
+        let write_authority = Pubkey::new_from_array(reader.take()?);
+        let verification_level = match reader.take::<1>()?[0] {
+            0 => VerificationLevel::Partial { num_signatures: reader.take::<1>()?[0] },
+            1 => VerificationLevel::Full,
+            _ => return err!(VaultError::InvalidOracleAccount),
+        };
+        let feed_id = reader.take()?;
+        let price = i64::from_le_bytes(reader.take()?);
+        let conf = u64::from_le_bytes(reader.take()?);
+        let exponent = i32::from_le_bytes(reader.take()?);
+        let publish_time = i64::from_le_bytes(reader.take()?);
+        let _prev_publish_time: [u8; 8] = reader.take()?;
+        let _ema_price: [u8; 8] = reader.take()?;
+        let _ema_conf: [u8; 8] = reader.take()?;
+        let posted_slot = u64::from_le_bytes(reader.take()?);
+        
+        Ok(PriceUpdate {
+            write_authority,
+            verification_level,
+            feed_id,
+            price,
+            conf,
+            exponent,
+            publish_time,
+            posted_slot,
+        })
+    }
+    
+    /// Sequential reader over account data
+    struct Reader<'a> {
+        data: &'a [u8],
+        offset: usize,
+    }
+    
+    impl Reader<'_> {
+        fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+            let end = self.offset + N;
+            require!(end <= self.data.len(), VaultError::InvalidOracleAccount);
+            let mut out = [0u8; N];
+            out.copy_from_slice(&self.data[self.offset..end]);
+            self.offset = end;
+            Ok(out)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{OracleProviderKind, OracleSource};
+    use pyth_receiver::{VerificationLevel, PRICE_UPDATE_V2_DISCRIMINATOR};
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn feed_config(require_full_verification: bool) -> OracleFeedConfig {
+        OracleFeedConfig {
+            bump: 255,
+            feed_id: pyth_feeds::SOL_USD,
+            max_staleness_secs: 60,
+            require_full_verification,
+            aggregation: OracleAggregation::Fallback,
+            min_sources: 1,
+            num_sources: 1,
+            sources: [OracleSource::default(); MAX_ORACLE_SOURCES],
+            reserved: [0; 64],
+        }
+    }
+
+    /// Feed backed by mock sources at `keys`
+    fn mock_feed(keys: &[Pubkey], aggregation: OracleAggregation, min_sources: u8) -> OracleFeedConfig {
+        let mut feed = feed_config(false);
+        feed.aggregation = aggregation;
+        feed.min_sources = min_sources;
+        feed.num_sources = keys.len() as u8;
+        for (source, key) in feed.sources.iter_mut().zip(keys) {
+            *source = OracleSource { provider: OracleProviderKind::Mock, account: *key };
+        }
+        feed
+    }
+
+    /// Serialize a mock price account
+    fn mock_price(price: i64, exponent: i32, publish_time: i64) -> Vec<u8> {
+        let mut data = price.to_le_bytes().to_vec();
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        data.extend_from_slice(&exponent.to_le_bytes());
+        data.extend_from_slice(&publish_time.to_le_bytes());
+        data
+    }
+
+    /// Aggregate a feed over mock accounts holding `datas`
+    fn aggregate(
+        keys: &[Pubkey],
+        datas: &mut [Vec<u8>],
+        feed: &OracleFeedConfig,
+        max_confidence_bps: u16,
+    ) -> Result<OraclePrice> {
+        let owner = Pubkey::new_unique();
+        let mut lamports = vec![1_000_000u64; datas.len()];
+        let infos: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(datas.iter_mut())
+            .map(|((key, lamports), data)| AccountInfo::new(key, false, false, lamports, data, &owner, false, 0))
+            .collect();
+        get_oracle_price(feed, &infos, max_confidence_bps, NOW)
+    }
+
+    /// Serialize a `PriceUpdateV2` account the way the receiver writes it
+    fn price_update(feed_id: [u8; 32], level: VerificationLevel, publish_time: i64) -> Vec<u8> {
+        let mut data = PRICE_UPDATE_V2_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[7u8; 32]); // write_authority
+        match level {
+            VerificationLevel::Partial { num_signatures } => data.extend_from_slice(&[0, num_signatures]),
+            VerificationLevel::Full => data.push(1),
+        }
+        data.extend_from_slice(&feed_id);
+        data.extend_from_slice(&15_000_000_000i64.to_le_bytes()); // price
+        data.extend_from_slice(&2_000_000u64.to_le_bytes()); // conf
+        data.extend_from_slice(&(-8i32).to_le_bytes()); // exponent
+        data.extend_from_slice(&publish_time.to_le_bytes());
+        data.extend_from_slice(&(publish_time - 1).to_le_bytes()); // prev_publish_time
+        data.extend_from_slice(&15_000_000_000i64.to_le_bytes()); // ema_price
+        data.extend_from_slice(&2_000_000u64.to_le_bytes()); // ema_conf
+        data.extend_from_slice(&42u64.to_le_bytes()); // posted_slot
+        data
+    }
+
+    fn read_price(owner: Pubkey, mut data: Vec<u8>, feed: &OracleFeedConfig) -> Result<OraclePrice> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000u64;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        get_pyth_price(&info, feed, NOW)
+    }
+
+    fn receiver() -> Pubkey {
+        Pubkey::from_str(PYTH_RECEIVER_PROGRAM_ID).unwrap()
+    }
+
+    #[test]
+    fn test_full_update_accepted() {
+        let data = price_update(pyth_feeds::SOL_USD, VerificationLevel::Full, NOW - 10);
+        let price = read_price(receiver(), data, &feed_config(true)).unwrap();
+        assert_eq!(price.price, 15_000_000_000);
+        assert_eq!(price.confidence, 2_000_000);
+        assert_eq!(price.exponent, -8);
+        assert_eq!(price.timestamp, NOW - 10);
+    }
+
+    #[test]
+    fn test_partial_update_layout() {
+        // The one-byte signature count shifts every following field
+        let level = VerificationLevel::Partial { num_signatures: 5 };
+        let data = price_update(pyth_feeds::SOL_USD, level, NOW);
+        let update = pyth_receiver::parse_price_update(&data).unwrap();
+        assert_eq!(update.verification_level, level);
+        assert_eq!(update.feed_id, pyth_feeds::SOL_USD);
+        assert_eq!(update.posted_slot, 42);
+
+        // Accepted when the feed does not require full verification
+        assert!(read_price(receiver(), data, &feed_config(false)).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_invalid_updates() {
+        let feed = feed_config(true);
+
+        let wrong_feed = price_update(pyth_feeds::BTC_USD, VerificationLevel::Full, NOW);
+        assert_eq!(
+            read_price(receiver(), wrong_feed, &feed).unwrap_err(),
+            VaultError::InvalidOracleAccount.into()
+        );
+
+        let partial = price_update(
+            pyth_feeds::SOL_USD,
+            VerificationLevel::Partial { num_signatures: 5 },
+            NOW,
+        );
+        assert_eq!(
+            read_price(receiver(), partial, &feed).unwrap_err(),
+            VaultError::OracleVerificationTooLow.into()
+        );
+
+        let stale = price_update(pyth_feeds::SOL_USD, VerificationLevel::Full, NOW - 61);
+        assert_eq!(
+            read_price(receiver(), stale, &feed).unwrap_err(),
+            VaultError::StaleOraclePrice.into()
+        );
+
+        let spoofed = price_update(pyth_feeds::SOL_USD, VerificationLevel::Full, NOW);
+        assert_eq!(
+            read_price(Pubkey::new_unique(), spoofed, &feed).unwrap_err(),
+            VaultError::InvalidOracleAccount.into()
+        );
+
+        let mut truncated = price_update(pyth_feeds::SOL_USD, VerificationLevel::Full, NOW);
+        truncated.truncate(60);
+        assert_eq!(
+            read_price(receiver(), truncated, &feed).unwrap_err(),
+            VaultError::InvalidOracleAccount.into()
+        );
+    }
+
+    #[test]
+    fn test_rescale() {
+        let price = OraclePrice { price: 15_000, confidence: 20, exponent: -2, timestamp: NOW };
+        let scaled = price.rescale(ORACLE_PRICE_EXPONENT).unwrap();
+        assert_eq!(scaled.price, 15_000_000_000);
+        assert_eq!(scaled.confidence, 20_000_000);
+        assert_eq!(scaled.rescale(-2).unwrap(), price);
+    }
+
+    #[test]
+    fn test_fallback_skips_failed_sources() {
+        let keys = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let feed = mock_feed(&keys, OracleAggregation::Fallback, 1);
+
+        // Primary is stale, the secondary answers
+        let mut datas = [mock_price(100, 0, NOW - 61), mock_price(15_000, -2, NOW), mock_price(1, 0, NOW)];
+        let price = aggregate(&keys, &mut datas, &feed, 0).unwrap();
+        assert_eq!(price.price, 15_000_000_000);
+        assert_eq!(price.exponent, ORACLE_PRICE_EXPONENT);
+
+        // Only when every source fails is the oracle unavailable
+        let mut datas = [mock_price(100, 0, NOW - 61), mock_price(0, 0, NOW), vec![0u8; 4]];
+        assert_eq!(
+            aggregate(&keys, &mut datas, &feed, 0).unwrap_err(),
+            VaultError::OracleUnavailable.into()
+        );
+    }
+
+    #[test]
+    fn test_median_and_quorum() {
+        let keys = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let feed = mock_feed(&keys, OracleAggregation::Median, 2);
+
+        let mut datas = [mock_price(152, 0, NOW), mock_price(150, 0, NOW), mock_price(149, 0, NOW)];
+        assert_eq!(aggregate(&keys, &mut datas, &feed, 0).unwrap().price, 15_000_000_000);
+
+        // Two valid prices average their middle pair
+        let mut datas = [mock_price(152, 0, NOW), mock_price(150, 0, NOW), mock_price(149, 0, NOW - 61)];
+        assert_eq!(aggregate(&keys, &mut datas, &feed, 0).unwrap().price, 15_100_000_000);
+
+        // One valid price does not meet the quorum
+        let mut datas = [mock_price(152, 0, NOW), mock_price(0, 0, NOW), mock_price(149, 0, NOW - 61)];
+        assert_eq!(
+            aggregate(&keys, &mut datas, &feed, 0).unwrap_err(),
+            VaultError::OracleQuorumNotMet.into()
+        );
+
+        // Sources read from unexpected accounts are ignored
+        let swapped = [keys[1], keys[0], keys[2]];
+        let mut datas = [mock_price(152, 0, NOW), mock_price(150, 0, NOW), mock_price(149, 0, NOW)];
+        assert_eq!(
+            aggregate(&swapped, &mut datas, &feed, 0).unwrap_err(),
+            VaultError::OracleQuorumNotMet.into()
+        );
+    }
+
+    #[test]
+    fn test_price_confidence() {
+        let price = |confidence| OraclePrice { price: 15_000_000_000, confidence, exponent: -8, timestamp: NOW };
+
+        // 2% of $150 is $3
+        assert!(validate_price_confidence(&price(300_000_000), 200).is_ok());
+        assert_eq!(
+            validate_price_confidence(&price(300_000_001), 200).unwrap_err(),
+            VaultError::OracleConfidenceTooWide.into()
+        );
+
+        // Zero disables the check
+        assert!(validate_price_confidence(&price(u64::MAX), 0).is_ok());
+    }
+
+    #[test]
+    fn test_wide_confidence_falls_back() {
+        let keys = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let feed = mock_feed(&keys, OracleAggregation::Fallback, 1);
+
+        // Mock confidence is 1_000 units: $10 on $150 (666 bps) for the
+        // primary, $0.01 on $150 (0.66 bps) for the secondary
+        let mut datas = [mock_price(15_000, -2, NOW), mock_price(15_000_000, -5, NOW)];
+        let price = aggregate(&keys, &mut datas, &feed, 200).unwrap();
+        assert_eq!(price.price, 15_000_000_000);
+        assert_eq!(price.confidence, 1_000_000);
+
+        // With no source inside the limit the price is refused
+        let mut datas = [mock_price(15_000, -2, NOW), mock_price(15_000, -2, NOW)];
+        assert_eq!(
+            aggregate(&keys, &mut datas, &feed, 200).unwrap_err(),
+            VaultError::OracleConfidenceTooWide.into()
+        );
+
+        // Median aggregation drops the wide source from the quorum
+        let feed = mock_feed(&keys, OracleAggregation::Median, 2);
+        let mut datas = [mock_price(15_000, -2, NOW), mock_price(15_000_000, -5, NOW)];
+        assert_eq!(
+            aggregate(&keys, &mut datas, &feed, 200).unwrap_err(),
+            VaultError::OracleQuorumNotMet.into()
+        );
+    }
 }
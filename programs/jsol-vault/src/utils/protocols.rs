@@ -1,29 +1,11 @@
 //! LST protocol helper functions
 //!
-//! Utilities for interacting with various LST protocols.
+//! Utilities for interacting with various LST protocols. Integration details
+//! (program, mint, pool and unstaking terms) come from the `ProtocolConfig`
+//! registry; only display names for the built-in protocols live here.
 
 use anchor_lang::prelude::*;
-use crate::state::LstProtocol;
-
-/// Get the token mint address for a protocol
-pub fn get_protocol_mint(protocol: LstProtocol) -> Option<&'static str> {
-    match protocol {
-        LstProtocol::Jito => Some("J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yYac6Y7kGCPn"),
-        LstProtocol::Marinade => Some("mSoLzYCxHdYgdzU16g5QSh3i5K3z3KZK7ytfqcJm7So"),
-        LstProtocol::BlazeStake => Some("bSo13r4TkiE4KumL71LsHTPpL2euBYLFx6h9HP3piy1"),
-        LstProtocol::Jupiter => Some("jupSoLaHXQiZZTSfEWMTRRgpnyFm8f6sZdosWBjx93v"),
-    }
-}
-
-/// Get the program ID for a protocol
-pub fn get_protocol_program_id(protocol: LstProtocol) -> Option<&'static str> {
-    match protocol {
-        LstProtocol::Marinade => Some("MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD"),
-        LstProtocol::Jito => Some("Jito4APyf642JPZPx3hGc6WWJ8zPKtRbRs4P815Awbb"),
-        LstProtocol::BlazeStake => Some("stk9ApL5HeVAwPLr3TLhDXdZS8ptVu7zp6ov8HFDuMi"),
-        LstProtocol::Jupiter => Some("jupSoLaHXQiZZTSfEWMTRRgpnyFm8f6sZdosWBjx93v"),
-    }
-}
+use crate::state::{AdapterKind, LstProtocol, ProtocolConfig};
 
 /// Get the protocol name as a string
 pub fn get_protocol_name(protocol: LstProtocol) -> &'static str {
@@ -46,26 +28,21 @@ pub fn get_protocol_symbol(protocol: LstProtocol) -> &'static str {
 }
 
 /// Get unstaking delay in seconds for a protocol
-pub fn get_unstaking_delay(protocol: LstProtocol) -> i64 {
-    match protocol {
-        LstProtocol::Jito => 0,           // Instant unstaking available
-        LstProtocol::Marinade => 0,       // mSOL has instant redemption (with fee) or delayed
-        LstProtocol::BlazeStake => 172800,// ~2 days
-        LstProtocol::Jupiter => 86400,    // ~1 day
-    }
+pub fn get_unstaking_delay(config: &ProtocolConfig) -> i64 {
+    config.unstake_delay
 }
 
 /// Check if protocol supports instant unstaking
-pub fn supports_instant_unstake(protocol: LstProtocol) -> bool {
-    matches!(protocol, LstProtocol::Jito | LstProtocol::Marinade)
+pub fn supports_instant_unstake(config: &ProtocolConfig) -> bool {
+    config.supports_instant_unstake
 }
 
 /// Get instant unstake fee in basis points (if applicable)
-pub fn get_instant_unstake_fee(protocol: LstProtocol) -> Option<u16> {
-    match protocol {
-        LstProtocol::Jito => Some(10),      // 0.1% fee
-        LstProtocol::Marinade => Some(30),  // 0.3% fee
-        _ => None,
+pub fn get_instant_unstake_fee(config: &ProtocolConfig) -> Option<u16> {
+    if config.supports_instant_unstake {
+        Some(config.instant_unstake_fee_bps)
+    } else {
+        None
     }
 }
 
@@ -93,22 +70,27 @@ pub fn index_to_protocol(index: u8) -> Option<LstProtocol> {
 /// Protocol configuration for display/analytics
 #[derive(Debug, Clone)]
 pub struct ProtocolInfo {
-    pub name: &'static str,
-    pub symbol: &'static str,
-    pub mint: Option<&'static str>,
-    pub program_id: Option<&'static str>,
+    pub protocol: u8,
+    pub name: Option<&'static str>,
+    pub symbol: Option<&'static str>,
+    pub adapter: AdapterKind,
+    pub mint: Pubkey,
+    pub program_id: Pubkey,
     pub unstaking_delay: i64,
     pub supports_instant: bool,
 }
 
-/// Get full protocol info
-pub fn get_protocol_info(protocol: LstProtocol) -> ProtocolInfo {
+/// Get full protocol info from its registry entry
+pub fn get_protocol_info(config: &ProtocolConfig) -> ProtocolInfo {
+    let builtin = index_to_protocol(config.protocol);
     ProtocolInfo {
-        name: get_protocol_name(protocol),
-        symbol: get_protocol_symbol(protocol),
-        mint: get_protocol_mint(protocol),
-        program_id: get_protocol_program_id(protocol),
-        unstaking_delay: get_unstaking_delay(protocol),
-        supports_instant: supports_instant_unstake(protocol),
+        protocol: config.protocol,
+        name: builtin.map(get_protocol_name),
+        symbol: builtin.map(get_protocol_symbol),
+        adapter: config.adapter,
+        mint: config.mint,
+        program_id: config.program_id,
+        unstaking_delay: get_unstaking_delay(config),
+        supports_instant: supports_instant_unstake(config),
     }
 }
@@ -0,0 +1,649 @@
+//! Generic SPL stake pool adapter
+//!
+//! Handles deposits, withdrawals and exchange rate reads for any LST backed
+//! by an SPL stake pool (jitoSOL, bSOL, JupSOL, Sanctum LSTs, ...).
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use spl_stake_pool::state::StakePool;
+
+use crate::constants::*;
+use crate::errors::VaultError;
+use crate::utils::adapter::{check_accounts, AdapterOperation, LstAdapter, VaultAccounts};
+
+/// Stake pool accounts shared by deposit and withdrawal instructions
+pub struct SplPoolAccounts<'a, 'info> {
+    /// Stake pool state account
+    pub stake_pool: &'a AccountInfo<'info>,
+    /// Stake pool withdraw authority PDA
+    pub withdraw_authority: &'a AccountInfo<'info>,
+    /// Reserve stake account
+    pub reserve_stake: &'a AccountInfo<'info>,
+    /// Manager fee token account
+    pub manager_fee_account: &'a AccountInfo<'info>,
+    /// Pool token mint
+    pub pool_mint: &'a AccountInfo<'info>,
+    /// Stake pool program
+    pub stake_pool_program: &'a AccountInfo<'info>,
+    /// Token program
+    pub token_program: &'a AccountInfo<'info>,
+}
+
+/// Additional stake pool accounts used by stake account deposits
+pub struct SplStakeDepositAccounts<'a, 'info> {
+    /// Validator list account
+    pub validator_list: &'a AccountInfo<'info>,
+    /// Pool stake deposit authority
+    pub deposit_authority: &'a AccountInfo<'info>,
+    /// Pool's stake account for the deposited stake's validator
+    pub validator_stake: &'a AccountInfo<'info>,
+    /// Clock sysvar
+    pub clock: &'a AccountInfo<'info>,
+    /// Stake history sysvar
+    pub stake_history: &'a AccountInfo<'info>,
+    /// Native stake program
+    pub stake_program: &'a AccountInfo<'info>,
+}
+
+/// Additional stake pool accounts used by stake account withdrawals
+pub struct SplStakeWithdrawAccounts<'a, 'info> {
+    /// Validator list account
+    pub validator_list: &'a AccountInfo<'info>,
+    /// Validator or reserve stake account to split from
+    pub stake_to_split: &'a AccountInfo<'info>,
+    /// Clock sysvar
+    pub clock: &'a AccountInfo<'info>,
+    /// Native stake program
+    pub stake_program: &'a AccountInfo<'info>,
+}
+
+/// Adapter for a single SPL stake pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplStakePoolAdapter {
+    /// Stake pool program (SPL stake pool or a compatible deployment)
+    pub program_id: Pubkey,
+    /// Stake pool state account
+    pub pool: Pubkey,
+    /// Pool token (LST) mint
+    pub mint: Pubkey,
+}
+
+impl SplStakePoolAdapter {
+    /// Create an adapter for the given pool
+    pub fn new(program_id: Pubkey, pool: Pubkey, mint: Pubkey) -> Self {
+        Self { program_id, pool, mint }
+    }
+
+    /// Derive the pool's withdraw authority PDA
+    pub fn withdraw_authority(&self) -> Pubkey {
+        spl_stake_pool::find_withdraw_authority_program_address(&self.program_id, &self.pool).0
+    }
+
+    /// Deserialize the stake pool state
+    ///
+    /// Verifies the account address, owner and pool mint first.
+    pub fn load_pool(&self, stake_pool: &AccountInfo) -> Result<StakePool> {
+        require_keys_eq!(stake_pool.key(), self.pool, VaultError::InvalidProtocolAccount);
+        require_keys_eq!(*stake_pool.owner, self.program_id, VaultError::InvalidAccountOwner);
+
+        let data = stake_pool.try_borrow_data()?;
+        let pool_state = StakePool::deserialize(&mut &data[..])
+            .map_err(|_| VaultError::ProtocolIntegrationFailed)?;
+        require!(pool_state.is_valid(), VaultError::AccountNotInitialized);
+        require_keys_eq!(pool_state.pool_mint, self.mint, VaultError::InvalidMint);
+
+        Ok(pool_state)
+    }
+
+    /// Validate the accounts passed for a deposit or withdrawal against the
+    /// on-chain pool state, returning the decoded state
+    pub fn validate_accounts(&self, accounts: &SplPoolAccounts) -> Result<StakePool> {
+        require_keys_eq!(
+            accounts.stake_pool_program.key(),
+            self.program_id,
+            VaultError::InvalidProtocolAccount
+        );
+
+        let pool_state = self.load_pool(accounts.stake_pool)?;
+        require_keys_eq!(
+            accounts.withdraw_authority.key(),
+            self.withdraw_authority(),
+            VaultError::InvalidProtocolAccount
+        );
+        require_keys_eq!(
+            accounts.reserve_stake.key(),
+            pool_state.reserve_stake,
+            VaultError::InvalidProtocolAccount
+        );
+        require_keys_eq!(
+            accounts.manager_fee_account.key(),
+            pool_state.manager_fee_account,
+            VaultError::InvalidProtocolAccount
+        );
+        require_keys_eq!(accounts.pool_mint.key(), self.mint, VaultError::InvalidMint);
+
+        Ok(pool_state)
+    }
+
+    /// Deposit SOL into the pool with `deposit_sol`
+    ///
+    /// `lamports_from` must be a system account signing through
+    /// `signer_seeds`. Pool tokens are minted to `pool_tokens_to`, which is
+    /// also passed as the referrer so referral fees return to the vault.
+    pub fn deposit_sol<'info>(
+        &self,
+        accounts: &SplPoolAccounts<'_, 'info>,
+        lamports_from: &AccountInfo<'info>,
+        pool_tokens_to: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+        lamports: u64,
+    ) -> Result<()> {
+        let ix = spl_stake_pool::instruction::deposit_sol(
+            &self.program_id,
+            &self.pool,
+            accounts.withdraw_authority.key,
+            accounts.reserve_stake.key,
+            lamports_from.key,
+            pool_tokens_to.key,
+            accounts.manager_fee_account.key,
+            pool_tokens_to.key,
+            &self.mint,
+            accounts.token_program.key,
+            lamports,
+        );
+
+        invoke_signed(
+            &ix,
+            &[
+                accounts.stake_pool.clone(),
+                accounts.withdraw_authority.clone(),
+                accounts.reserve_stake.clone(),
+                lamports_from.clone(),
+                pool_tokens_to.clone(),
+                accounts.manager_fee_account.clone(),
+                accounts.pool_mint.clone(),
+                system_program.clone(),
+                accounts.token_program.clone(),
+                accounts.stake_pool_program.clone(),
+            ],
+            signer_seeds,
+        )
+        .map_err(|_| VaultError::ProtocolIntegrationFailed)?;
+
+        Ok(())
+    }
+
+    /// Deposit a stake account into the pool with `deposit_stake`
+    ///
+    /// `stake_authority` is the current staker and withdrawer of
+    /// `stake_account` and signs through `signer_seeds`; both authorities
+    /// are moved to the pool's deposit authority before the deposit. Pool
+    /// tokens are minted to `pool_tokens_to`, which is also the referrer.
+    #[allow(clippy::too_many_arguments)]
+    pub fn deposit_stake_account<'info>(
+        &self,
+        accounts: &SplPoolAccounts<'_, 'info>,
+        stake_accounts: &SplStakeDepositAccounts<'_, 'info>,
+        stake_account: &AccountInfo<'info>,
+        stake_authority: &AccountInfo<'info>,
+        pool_tokens_to: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let instructions = spl_stake_pool::instruction::deposit_stake(
+            &self.program_id,
+            &self.pool,
+            stake_accounts.validator_list.key,
+            accounts.withdraw_authority.key,
+            stake_account.key,
+            stake_authority.key,
+            stake_accounts.validator_stake.key,
+            accounts.reserve_stake.key,
+            pool_tokens_to.key,
+            accounts.manager_fee_account.key,
+            pool_tokens_to.key,
+            &self.mint,
+            accounts.token_program.key,
+        );
+
+        let account_infos = [
+            accounts.stake_pool.clone(),
+            stake_accounts.validator_list.clone(),
+            stake_accounts.deposit_authority.clone(),
+            accounts.withdraw_authority.clone(),
+            stake_account.clone(),
+            stake_authority.clone(),
+            stake_accounts.validator_stake.clone(),
+            accounts.reserve_stake.clone(),
+            pool_tokens_to.clone(),
+            accounts.manager_fee_account.clone(),
+            accounts.pool_mint.clone(),
+            stake_accounts.clock.clone(),
+            stake_accounts.stake_history.clone(),
+            accounts.token_program.clone(),
+            stake_accounts.stake_program.clone(),
+            accounts.stake_pool_program.clone(),
+        ];
+        for ix in instructions.iter() {
+            invoke_signed(ix, &account_infos, signer_seeds)
+                .map_err(|_| VaultError::ProtocolIntegrationFailed)?;
+        }
+
+        Ok(())
+    }
+
+    /// Redeem pool tokens for a stake account with `withdraw_stake`
+    ///
+    /// `transfer_authority` owns `pool_tokens_from` and signs through
+    /// `signer_seeds`. Stake is split from `stake_to_split` into the
+    /// uninitialized `stake_to_receive`, whose staker and withdrawer are set
+    /// to `stake_authority`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn withdraw_stake_account<'info>(
+        &self,
+        accounts: &SplPoolAccounts<'_, 'info>,
+        stake_accounts: &SplStakeWithdrawAccounts<'_, 'info>,
+        stake_to_receive: &AccountInfo<'info>,
+        stake_authority: &AccountInfo<'info>,
+        transfer_authority: &AccountInfo<'info>,
+        pool_tokens_from: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+        pool_tokens: u64,
+    ) -> Result<()> {
+        let ix = spl_stake_pool::instruction::withdraw_stake(
+            &self.program_id,
+            &self.pool,
+            stake_accounts.validator_list.key,
+            accounts.withdraw_authority.key,
+            stake_accounts.stake_to_split.key,
+            stake_to_receive.key,
+            stake_authority.key,
+            transfer_authority.key,
+            pool_tokens_from.key,
+            accounts.manager_fee_account.key,
+            &self.mint,
+            accounts.token_program.key,
+            pool_tokens,
+        );
+
+        invoke_signed(
+            &ix,
+            &[
+                accounts.stake_pool.clone(),
+                stake_accounts.validator_list.clone(),
+                accounts.withdraw_authority.clone(),
+                stake_accounts.stake_to_split.clone(),
+                stake_to_receive.clone(),
+                stake_authority.clone(),
+                transfer_authority.clone(),
+                pool_tokens_from.clone(),
+                accounts.manager_fee_account.clone(),
+                accounts.pool_mint.clone(),
+                stake_accounts.clock.clone(),
+                accounts.token_program.clone(),
+                stake_accounts.stake_program.clone(),
+                accounts.stake_pool_program.clone(),
+            ],
+            signer_seeds,
+        )
+        .map_err(|_| VaultError::ProtocolIntegrationFailed)?;
+
+        Ok(())
+    }
+
+    /// Redeem pool tokens for SOL from the reserve with `withdraw_sol`
+    ///
+    /// `transfer_authority` owns `pool_tokens_from` and signs through
+    /// `signer_seeds`. Lamports are sent to `lamports_to`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn withdraw_sol<'info>(
+        &self,
+        accounts: &SplPoolAccounts<'_, 'info>,
+        transfer_authority: &AccountInfo<'info>,
+        pool_tokens_from: &AccountInfo<'info>,
+        lamports_to: &AccountInfo<'info>,
+        clock: &AccountInfo<'info>,
+        stake_history: &AccountInfo<'info>,
+        stake_program: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+        pool_tokens: u64,
+    ) -> Result<()> {
+        let ix = spl_stake_pool::instruction::withdraw_sol(
+            &self.program_id,
+            &self.pool,
+            accounts.withdraw_authority.key,
+            transfer_authority.key,
+            pool_tokens_from.key,
+            accounts.reserve_stake.key,
+            lamports_to.key,
+            accounts.manager_fee_account.key,
+            &self.mint,
+            accounts.token_program.key,
+            pool_tokens,
+        );
+
+        invoke_signed(
+            &ix,
+            &[
+                accounts.stake_pool.clone(),
+                accounts.withdraw_authority.clone(),
+                transfer_authority.clone(),
+                pool_tokens_from.clone(),
+                accounts.reserve_stake.clone(),
+                lamports_to.clone(),
+                accounts.manager_fee_account.clone(),
+                accounts.pool_mint.clone(),
+                clock.clone(),
+                stake_history.clone(),
+                stake_program.clone(),
+                accounts.token_program.clone(),
+                accounts.stake_pool_program.clone(),
+            ],
+            signer_seeds,
+        )
+        .map_err(|_| VaultError::ProtocolIntegrationFailed)?;
+
+        Ok(())
+    }
+
+    /// Read the pool exchange rate (lamports per pool token)
+    ///
+    /// Returns exchange rate scaled by SHARE_PRECISION
+    pub fn exchange_rate(&self, stake_pool: &AccountInfo) -> Result<u64> {
+        let pool_state = self.load_pool(stake_pool)?;
+        pool_exchange_rate(&pool_state)
+    }
+}
+
+/// Protocol accounts, in `remaining_accounts` order:
+///
+/// 0. Stake pool state account (writable)
+/// 1. Stake pool withdraw authority PDA
+/// 2. Reserve stake account (writable)
+/// 3. Manager fee token account (writable)
+/// 4. Pool token mint (writable)
+/// 5. Stake pool program
+/// 6. Clock sysvar (liquid unstake and stake deposit/withdrawal only)
+/// 7. Stake history sysvar (liquid unstake and stake deposit/withdrawal only)
+/// 8. Native stake program (liquid unstake and stake deposit/withdrawal only)
+/// 9. Validator list (writable, stake deposit/withdrawal only)
+/// 10. Stake deposit authority PDA (stake deposit), or validator or reserve
+///     stake account to split from (writable, stake withdrawal)
+/// 11. Pool validator stake account (writable, stake deposit only)
+///
+/// Only pools using the default (PDA) stake deposit authority accept stake
+/// account deposits.
+///
+/// SPL stake pools have no delayed unstake ticket, so delayed unstaking and
+/// claims are unsupported.
+impl LstAdapter for SplStakePoolAdapter {
+    fn required_accounts(&self, operation: AdapterOperation) -> usize {
+        match operation {
+            AdapterOperation::Deposit => 6,
+            AdapterOperation::DepositStake => 12,
+            AdapterOperation::WithdrawStake => 11,
+            AdapterOperation::LiquidUnstake => 9,
+            AdapterOperation::DelayedUnstake | AdapterOperation::Claim => 0,
+            AdapterOperation::ExchangeRate => 1,
+        }
+    }
+
+    fn supports_delayed_unstake(&self) -> bool {
+        false
+    }
+
+    fn ticket_layout(&self) -> Option<(usize, Pubkey)> {
+        None
+    }
+
+    fn ticket_lamports(&self, _vault: &VaultAccounts, _ticket_account: &AccountInfo) -> Result<u64> {
+        err!(VaultError::UnsupportedProtocol)
+    }
+
+    fn deposit<'info>(
+        &self,
+        vault: &VaultAccounts<'_, 'info>,
+        accounts: &[AccountInfo<'info>],
+        lamports: u64,
+    ) -> Result<()> {
+        let accounts = check_accounts(self, AdapterOperation::Deposit, accounts)?;
+        let pool_accounts = pool_accounts(accounts, vault.token_program);
+        self.validate_accounts(&pool_accounts)?;
+
+        let vault_sol_seeds: &[&[u8]] = &[VAULT_SOL_SEED, &[vault.vault_sol_bump]];
+        self.deposit_sol(
+            &pool_accounts,
+            vault.vault_sol,
+            vault.vault_token_account,
+            vault.system_program,
+            &[vault_sol_seeds],
+            lamports,
+        )
+    }
+
+    fn deposit_stake<'info>(
+        &self,
+        vault: &VaultAccounts<'_, 'info>,
+        stake_account: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let accounts = check_accounts(self, AdapterOperation::DepositStake, accounts)?;
+        let pool_accounts = pool_accounts(accounts, vault.token_program);
+        let pool_state = self.validate_accounts(&pool_accounts)?;
+        let stake_accounts = SplStakeDepositAccounts {
+            validator_list: &accounts[9],
+            deposit_authority: &accounts[10],
+            validator_stake: &accounts[11],
+            clock: &accounts[6],
+            stake_history: &accounts[7],
+            stake_program: &accounts[8],
+        };
+
+        require_keys_eq!(
+            stake_accounts.validator_list.key(),
+            pool_state.validator_list,
+            VaultError::InvalidProtocolAccount
+        );
+        require_keys_eq!(
+            stake_accounts.deposit_authority.key(),
+            pool_state.stake_deposit_authority,
+            VaultError::InvalidProtocolAccount
+        );
+        // A custom deposit authority would have to co-sign the deposit
+        require_keys_eq!(
+            pool_state.stake_deposit_authority,
+            spl_stake_pool::find_deposit_authority_program_address(&self.program_id, &self.pool).0,
+            VaultError::UnsupportedProtocol
+        );
+        require_keys_eq!(
+            stake_accounts.stake_program.key(),
+            anchor_lang::solana_program::stake::program::ID,
+            VaultError::InvalidProtocolAccount
+        );
+
+        let vault_seeds: &[&[u8]] = &[VAULT_SEED, &[vault.vault_bump]];
+        self.deposit_stake_account(
+            &pool_accounts,
+            &stake_accounts,
+            stake_account,
+            vault.vault,
+            vault.vault_token_account,
+            &[vault_seeds],
+        )
+    }
+
+    fn withdraw_stake<'info>(
+        &self,
+        vault: &VaultAccounts<'_, 'info>,
+        stake_account: &AccountInfo<'info>,
+        stake_authority: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+        lst_amount: u64,
+    ) -> Result<()> {
+        let accounts = check_accounts(self, AdapterOperation::WithdrawStake, accounts)?;
+        let pool_accounts = pool_accounts(accounts, vault.token_program);
+        let pool_state = self.validate_accounts(&pool_accounts)?;
+        let stake_accounts = SplStakeWithdrawAccounts {
+            validator_list: &accounts[9],
+            stake_to_split: &accounts[10],
+            clock: &accounts[6],
+            stake_program: &accounts[8],
+        };
+
+        // The pool checks the split source against its validator list
+        require_keys_eq!(
+            stake_accounts.validator_list.key(),
+            pool_state.validator_list,
+            VaultError::InvalidProtocolAccount
+        );
+        require_keys_eq!(
+            stake_accounts.stake_program.key(),
+            anchor_lang::solana_program::stake::program::ID,
+            VaultError::InvalidProtocolAccount
+        );
+
+        let vault_seeds: &[&[u8]] = &[VAULT_SEED, &[vault.vault_bump]];
+        self.withdraw_stake_account(
+            &pool_accounts,
+            &stake_accounts,
+            stake_account,
+            stake_authority,
+            vault.vault,
+            vault.vault_token_account,
+            &[vault_seeds],
+            lst_amount,
+        )
+    }
+
+    fn liquid_unstake<'info>(
+        &self,
+        vault: &VaultAccounts<'_, 'info>,
+        accounts: &[AccountInfo<'info>],
+        lst_amount: u64,
+    ) -> Result<()> {
+        let accounts = check_accounts(self, AdapterOperation::LiquidUnstake, accounts)?;
+        let pool_accounts = pool_accounts(accounts, vault.token_program);
+        self.validate_accounts(&pool_accounts)?;
+        require_keys_eq!(
+            accounts[8].key(),
+            anchor_lang::solana_program::stake::program::ID,
+            VaultError::InvalidProtocolAccount
+        );
+
+        let vault_seeds: &[&[u8]] = &[VAULT_SEED, &[vault.vault_bump]];
+        self.withdraw_sol(
+            &pool_accounts,
+            vault.vault,
+            vault.vault_token_account,
+            vault.vault_sol,
+            &accounts[6],
+            &accounts[7],
+            &accounts[8],
+            &[vault_seeds],
+            lst_amount,
+        )
+    }
+
+    fn delayed_unstake<'info>(
+        &self,
+        _vault: &VaultAccounts<'_, 'info>,
+        _ticket_account: &AccountInfo<'info>,
+        _accounts: &[AccountInfo<'info>],
+        _lst_amount: u64,
+    ) -> Result<()> {
+        err!(VaultError::UnsupportedProtocol)
+    }
+
+    fn claim<'info>(
+        &self,
+        _vault: &VaultAccounts<'_, 'info>,
+        _ticket_account: &AccountInfo<'info>,
+        _accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        err!(VaultError::UnsupportedProtocol)
+    }
+
+    fn exchange_rate(&self, accounts: &[AccountInfo]) -> Result<u64> {
+        let accounts = check_accounts(self, AdapterOperation::ExchangeRate, accounts)?;
+        SplStakePoolAdapter::exchange_rate(self, &accounts[0])
+    }
+}
+
+/// Map the leading protocol accounts onto `SplPoolAccounts`
+fn pool_accounts<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+    token_program: &'a AccountInfo<'info>,
+) -> SplPoolAccounts<'a, 'info> {
+    SplPoolAccounts {
+        stake_pool: &accounts[0],
+        withdraw_authority: &accounts[1],
+        reserve_stake: &accounts[2],
+        manager_fee_account: &accounts[3],
+        pool_mint: &accounts[4],
+        stake_pool_program: &accounts[5],
+        token_program,
+    }
+}
+
+/// Exchange rate of a decoded stake pool (lamports per pool token)
+///
+/// Returns exchange rate scaled by SHARE_PRECISION
+pub fn pool_exchange_rate(pool_state: &StakePool) -> Result<u64> {
+    if pool_state.pool_token_supply == 0 {
+        return Ok(SHARE_PRECISION);
+    }
+
+    let rate = (pool_state.total_lamports as u128)
+        .checked_mul(SHARE_PRECISION as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(pool_state.pool_token_supply as u128)
+        .ok_or(VaultError::DivisionByZero)?;
+
+    Ok(rate as u64)
+}
+
+/// Convert a lamport amount into pool tokens at the pool's current rate
+pub fn lamports_to_pool_tokens(pool_state: &StakePool, lamports: u64) -> Result<u64> {
+    if pool_state.total_lamports == 0 || pool_state.pool_token_supply == 0 {
+        return Ok(lamports);
+    }
+
+    let tokens = (lamports as u128)
+        .checked_mul(pool_state.pool_token_supply as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(pool_state.total_lamports as u128)
+        .ok_or(VaultError::DivisionByZero)?;
+
+    Ok(tokens as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(total_lamports: u64, pool_token_supply: u64) -> StakePool {
+        StakePool {
+            total_lamports,
+            pool_token_supply,
+            ..StakePool::default()
+        }
+    }
+
+    #[test]
+    fn test_pool_exchange_rate() {
+        // Empty pool trades 1:1
+        assert_eq!(pool_exchange_rate(&pool(0, 0)).unwrap(), SHARE_PRECISION);
+
+        // 105 SOL backing 100 pool tokens = 1.05
+        assert_eq!(pool_exchange_rate(&pool(105, 100)).unwrap(), 1_050_000_000);
+    }
+
+    #[test]
+    fn test_lamports_to_pool_tokens() {
+        // 105 lamports at 1.05 = 100 pool tokens
+        assert_eq!(lamports_to_pool_tokens(&pool(105, 100), 105).unwrap(), 100);
+
+        // Rounds down in the vault's favor
+        assert_eq!(lamports_to_pool_tokens(&pool(105, 100), 104).unwrap(), 99);
+    }
+}
@@ -0,0 +1,137 @@
+//! Switchboard On-Demand oracle provider
+//!
+//! Reads the current result of a Switchboard pull feed. Feed accounts are
+//! zero-copy `PullFeedAccountData`, so fields are read at fixed offsets.
+
+use anchor_lang::prelude::*;
+use std::str::FromStr;
+
+use crate::constants::*;
+use crate::errors::VaultError;
+use crate::state::OracleFeedConfig;
+use crate::utils::oracle::{validate_price_age, OracleProvider, OraclePrice};
+
+/// Anchor discriminator for the `PullFeedAccountData` account
+pub const PULL_FEED_DISCRIMINATOR: [u8; 8] = [196, 27, 108, 196, 10, 215, 219, 40];
+
+/// Decimal places of Switchboard result values
+pub const SWITCHBOARD_PRECISION: u32 = 18;
+
+/// Byte offsets into `PullFeedAccountData` (including the discriminator)
+pub mod pull_feed_offsets {
+    /// `last_update_timestamp: i64`
+    pub const LAST_UPDATE_TIMESTAMP: usize = 8 + 2208;
+    /// `result.value: i128`
+    pub const RESULT_VALUE: usize = 8 + 2256;
+    /// `result.std_dev: i128`
+    pub const RESULT_STD_DEV: usize = 8 + 2272;
+    /// `result.slot: u64`
+    pub const RESULT_SLOT: usize = 8 + 2360;
+}
+
+/// Switchboard On-Demand pull feed provider
+pub struct SwitchboardProvider;
+
+impl OracleProvider for SwitchboardProvider {
+    fn get_price(
+        &self,
+        account: &AccountInfo,
+        feed: &OracleFeedConfig,
+        current_time: i64,
+    ) -> Result<OraclePrice> {
+        let program = Pubkey::from_str(SWITCHBOARD_ON_DEMAND_PROGRAM_ID)
+            .map_err(|_| VaultError::InvalidOracleAccount)?;
+        require_keys_eq!(*account.owner, program, VaultError::InvalidOracleAccount);
+
+        let data = account.try_borrow_data()?;
+        require!(
+            data.len() >= pull_feed_offsets::RESULT_SLOT + 8
+                && data[..8] == PULL_FEED_DISCRIMINATOR,
+            VaultError::InvalidOracleAccount
+        );
+
+        let read_i128 = |offset: usize| i128::from_le_bytes(data[offset..offset + 16].try_into().unwrap());
+        let timestamp = i64::from_le_bytes(
+            data[pull_feed_offsets::LAST_UPDATE_TIMESTAMP..pull_feed_offsets::LAST_UPDATE_TIMESTAMP + 8]
+                .try_into()
+                .unwrap(),
+        );
+        let value = read_i128(pull_feed_offsets::RESULT_VALUE);
+        let std_dev = read_i128(pull_feed_offsets::RESULT_STD_DEV);
+
+        validate_price_age(timestamp, current_time, feed.max_staleness_secs)?;
+        require!(value > 0 && std_dev >= 0, VaultError::InvalidOracleAccount);
+
+        // Results carry 18 decimals, which overflow u64 for most prices
+        let divisor = 10i128.pow(SWITCHBOARD_PRECISION - ORACLE_PRICE_EXPONENT.unsigned_abs());
+        let to_u64 = |v: i128| u64::try_from(v / divisor).map_err(|_| error!(VaultError::MathOverflow));
+
+        Ok(OraclePrice {
+            price: to_u64(value)?,
+            confidence: to_u64(std_dev)?,
+            exponent: ORACLE_PRICE_EXPONENT,
+            timestamp,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::OracleSource;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn feed() -> OracleFeedConfig {
+        OracleFeedConfig {
+            bump: 255,
+            feed_id: [0; 32],
+            max_staleness_secs: 60,
+            require_full_verification: false,
+            aggregation: Default::default(),
+            min_sources: 1,
+            num_sources: 1,
+            sources: [OracleSource::default(); MAX_ORACLE_SOURCES],
+            reserved: [0; 64],
+        }
+    }
+
+    /// Zeroed pull feed with the given result and update time
+    fn pull_feed(value: i128, std_dev: i128, last_update: i64) -> Vec<u8> {
+        let mut data = vec![0u8; 8 + 3200];
+        data[..8].copy_from_slice(&PULL_FEED_DISCRIMINATOR);
+        data[pull_feed_offsets::LAST_UPDATE_TIMESTAMP..][..8].copy_from_slice(&last_update.to_le_bytes());
+        data[pull_feed_offsets::RESULT_VALUE..][..16].copy_from_slice(&value.to_le_bytes());
+        data[pull_feed_offsets::RESULT_STD_DEV..][..16].copy_from_slice(&std_dev.to_le_bytes());
+        data
+    }
+
+    fn read(owner: Pubkey, mut data: Vec<u8>) -> Result<OraclePrice> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000u64;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        SwitchboardProvider.get_price(&info, &feed(), NOW)
+    }
+
+    #[test]
+    fn test_pull_feed_price() {
+        let program = Pubkey::from_str(SWITCHBOARD_ON_DEMAND_PROGRAM_ID).unwrap();
+
+        // $150.25 +/- $0.05 with 18 decimals
+        let data = pull_feed(150_250_000_000_000_000_000, 50_000_000_000_000_000, NOW - 5);
+        let price = read(program, data).unwrap();
+        assert_eq!(price.price, 15_025_000_000);
+        assert_eq!(price.confidence, 5_000_000);
+        assert_eq!(price.exponent, ORACLE_PRICE_EXPONENT);
+        assert_eq!(price.timestamp, NOW - 5);
+
+        let stale = pull_feed(150_000_000_000_000_000_000, 0, NOW - 61);
+        assert_eq!(read(program, stale).unwrap_err(), VaultError::StaleOraclePrice.into());
+
+        let spoofed = pull_feed(150_000_000_000_000_000_000, 0, NOW);
+        assert_eq!(
+            read(Pubkey::new_unique(), spoofed).unwrap_err(),
+            VaultError::InvalidOracleAccount.into()
+        );
+    }
+}
@@ -1,5 +1,7 @@
 //! Input validation utilities
 
+use std::str::FromStr;
+
 use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::VaultError;
@@ -81,9 +83,78 @@ pub fn validate_deposit_cap(current_tvl: u64, deposit: u64, cap: u64) -> Result<
     Ok(())
 }
 
+/// Validate that a deposit keeps a protocol within its allocation cap
+/// 
+/// `allocation` is the protocol's current value and `current_tvl` the
+/// vault's, both before the deposit.
+pub fn validate_allocation_cap(allocation: u64, current_tvl: u64, deposit: u64) -> Result<()> {
+    let new_allocation = allocation
+        .checked_add(deposit)
+        .ok_or(VaultError::MathOverflow)?;
+    let new_tvl = current_tvl
+        .checked_add(deposit)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    let new_allocation_bps = (new_allocation as u128)
+        .checked_mul(BPS_DENOMINATOR as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(new_tvl as u128)
+        .ok_or(VaultError::DivisionByZero)?;
+    
+    require!(
+        new_allocation_bps <= MAX_PROTOCOL_ALLOCATION_BPS as u128,
+        VaultError::AllocationExceedsMax
+    );
+    Ok(())
+}
+
 /// Validate that a protocol ID is valid
 pub fn validate_protocol_id(protocol: u8) -> Result<()> {
-    // Protocol IDs 0-3 are valid (Jito, Marinade, BlazeStake, Jupiter)
-    require!(protocol <= 3, VaultError::UnsupportedProtocol);
+    // Protocol IDs index the allocation slots; SPL pool LSTs beyond the
+    // built-in four are registered at runtime
+    require!((protocol as usize) < MAX_PROTOCOLS, VaultError::UnsupportedProtocol);
+    Ok(())
+}
+
+/// Validate that an account key matches an expected base58 address
+pub fn validate_address(actual: &Pubkey, expected: &str) -> Result<()> {
+    let expected = Pubkey::from_str(expected)
+        .map_err(|_| VaultError::InvalidProtocolAccount)?;
+    require_keys_eq!(*actual, expected, VaultError::InvalidProtocolAccount);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_validate_deposit_cap() {
+        // No cap
+        assert!(validate_deposit_cap(u64::MAX - 1, 1, 0).is_ok());
+        
+        // Exactly at the cap
+        assert!(validate_deposit_cap(900, 100, 1000).is_ok());
+        
+        // Over the cap
+        assert!(validate_deposit_cap(901, 100, 1000).is_err());
+    }
+    
+    #[test]
+    fn test_validate_allocation_cap() {
+        // 400 of 1000 plus 100 = 500 of 1100, under 50%
+        assert!(validate_allocation_cap(400, 1000, 100).is_ok());
+        
+        // 400 of 900 plus 100 = 500 of 1000, exactly 50%
+        assert!(validate_allocation_cap(400, 900, 100).is_ok());
+        
+        // 500 of 900 plus 100 = 600 of 1000, over 50%
+        assert_eq!(
+            validate_allocation_cap(500, 900, 100).unwrap_err(),
+            VaultError::AllocationExceedsMax.into()
+        );
+        
+        // A single protocol can't take a first deposit into an empty vault
+        assert!(validate_allocation_cap(0, 0, 100).is_err());
+    }
+}
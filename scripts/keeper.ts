@@ -1,7 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { JsolVault } from "../target/types/jsol_vault";
-import { PublicKey } from "@solana/web3.js";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
 
 // Configuration
 const KEEPER_KEY_PATH = process.env.KEEPER_KEY_PATH || "~/.config/solana/id.json";
@@ -29,6 +29,10 @@ async function main() {
         [Buffer.from("vault")],
         program.programId
     );
+    const [vaultSolPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault_sol")],
+        program.programId
+    );
 
     try {
         const vaultState = await program.account.vaultState.fetch(vaultPda);
@@ -77,11 +81,17 @@ async function main() {
             console.log("Threshold exceeded. Triggering rebalance...");
 
             try {
+                // Each moved allocation also needs its account group
+                // ([protocol_config, vault_token_account, ...adapter accounts])
+                // in remainingAccounts, overweight protocols first.
                 const tx = await program.methods
                     .rebalance()
                     .accounts({
                         vault: vaultPda,
+                        vaultSolAccount: vaultSolPda,
                         rebalancer: provider.wallet.publicKey,
+                        systemProgram: SystemProgram.programId,
+                        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
                     } as any)
                     .rpc();
 
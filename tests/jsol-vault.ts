@@ -71,6 +71,14 @@ describe("jsol-vault", () => {
       owner: user,
     });
 
+    const solUsdFeedId = Buffer.from(
+      "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
+      "hex"
+    );
+    const [oracleFeedPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle_feed"), solUsdFeedId],
+      program.programId
+    );
     const mockOracle = anchor.web3.Keypair.generate().publicKey;
 
     try {
@@ -83,6 +91,7 @@ describe("jsol-vault", () => {
           userAccount: userAccountPda,
           vaultSolAccount: vaultSolPda,
           user: user,
+          oracleFeed: oracleFeedPda,
           oracleAccount: mockOracle,
           tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
@@ -94,4 +103,88 @@ describe("jsol-vault", () => {
       console.log("Deposit call attempted - expected failure since oracle is mock.");
     }
   });
+
+  it("Cancels a withdrawal request and restores the position", async () => {
+    const user = provider.wallet.publicKey;
+    const shares = new anchor.BN(1_000_000); // 0.001 jSOLi
+
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("vault")], program.programId);
+    const [mintPda] = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("jsoli_mint")], program.programId);
+    const [vaultSolPda] = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("vault_sol")], program.programId);
+    const [escrowPda] = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("withdraw_escrow")], program.programId);
+    const [userAccountPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user"), user.toBuffer()],
+      program.programId
+    );
+    const userJsolAccount = anchor.utils.token.associatedAddress({
+      mint: mintPda,
+      owner: user,
+    });
+
+    const before = await program.account.userAccount.fetchNullable(userAccountPda);
+    if (!before || before.shares.lt(shares)) {
+      console.log("No deposited shares to withdraw - skipping since deposits need a live oracle.");
+      return;
+    }
+
+    const [requestPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("withdraw"),
+        user.toBuffer(),
+        before.nextRequestIndex.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+
+    // Registry entries of the vault's allocations, for the funding delay
+    const vault = await program.account.vaultState.fetch(vaultPda);
+    const protocolConfigs = vault.allocations
+      .slice(0, vault.numAllocations)
+      .map((alloc) => ({
+        pubkey: anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("protocol"), Buffer.from([alloc.protocol])],
+          program.programId
+        )[0],
+        isWritable: false,
+        isSigner: false,
+      }));
+
+    await program.methods
+      .requestWithdraw(shares, null)
+      .accounts({
+        vault: vaultPda,
+        userAccount: userAccountPda,
+        withdrawRequest: requestPda,
+        jsoliMint: mintPda,
+        userJsolAccount: userJsolAccount,
+        withdrawEscrow: escrowPda,
+        vaultSolAccount: vaultSolPda,
+        protocolConfig: null,
+        vaultLstAccount: null,
+        user: user,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(protocolConfigs)
+      .rpc();
+
+    await program.methods
+      .cancelWithdraw()
+      .accounts({
+        vault: vaultPda,
+        jsoliMint: mintPda,
+        userJsolAccount: userJsolAccount,
+        withdrawEscrow: escrowPda,
+        userAccount: userAccountPda,
+        withdrawRequest: requestPda,
+        user: user,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const after = await program.account.userAccount.fetch(userAccountPda);
+    expect(after.shares.toString()).to.equal(before.shares.toString());
+    expect(after.pendingWithdrawals).to.equal(before.pendingWithdrawals);
+    expect(await program.account.withdrawRequest.fetchNullable(requestPda)).to.be.null;
+  });
 });